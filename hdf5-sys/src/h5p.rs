@@ -371,6 +371,8 @@ extern "C" {
     ) -> herr_t;
     pub fn H5Pget_buffer(plist_id: hid_t, tconv: *mut *mut c_void, bkg: *mut *mut c_void)
         -> size_t;
+    pub fn H5Pset_preserve(plist_id: hid_t, status: hbool_t) -> herr_t;
+    pub fn H5Pget_preserve(plist_id: hid_t) -> c_int;
     pub fn H5Pset_edc_check(plist_id: hid_t, check: H5Z_EDC_t) -> herr_t;
     pub fn H5Pget_edc_check(plist_id: hid_t) -> H5Z_EDC_t;
     pub fn H5Pset_filter_callback(
@@ -2,6 +2,9 @@ use std::mem;
 use std::os::raw::c_void;
 use std::ptr;
 
+#[cfg(feature = "complex")]
+use num_complex::Complex;
+
 use crate::array::{Array, VarLenArray};
 use crate::string::{FixedAscii, FixedUnicode, VarLenAscii, VarLenUnicode};
 
@@ -138,6 +141,10 @@ impl CompoundType {
     }
 }
 
+/// Size of a dataset region reference in bytes, i.e. of HDF5's `hdset_reg_ref_t` buffer
+/// (`H5R_DSET_REG_REF_BUF_SIZE`: an 8-byte heap address followed by a 4-byte heap index).
+const REGION_REFERENCE_SIZE: usize = 12;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TypeDescriptor {
     Integer(IntSize),
@@ -152,6 +159,7 @@ pub enum TypeDescriptor {
     VarLenArray(Box<TypeDescriptor>),
     VarLenAscii,
     VarLenUnicode,
+    RegionReference,
 }
 
 impl TypeDescriptor {
@@ -168,6 +176,7 @@ impl TypeDescriptor {
             FixedAscii(len) | FixedUnicode(len) => len,
             VarLenArray(_) => mem::size_of::<hvl_t>(),
             VarLenAscii | VarLenUnicode => mem::size_of::<*const u8>(),
+            RegionReference => REGION_REFERENCE_SIZE,
         }
     }
 
@@ -179,7 +188,7 @@ impl TypeDescriptor {
                 compound.fields.iter().map(|f| f.ty.c_alignment()).max().unwrap_or(1)
             }
             FixedArray(ref ty, _) => ty.c_alignment(),
-            FixedAscii(_) | FixedUnicode(_) => 1,
+            FixedAscii(_) | FixedUnicode(_) | RegionReference => 1,
             VarLenArray(_) => mem::size_of::<usize>(),
             _ => self.size(),
         }
@@ -348,6 +357,29 @@ unsafe impl H5Type for VarLenUnicode {
     }
 }
 
+// Complex numbers are stored as compound types with "r" and "i" fields (same as in h5py).
+#[cfg(feature = "complex")]
+macro_rules! impl_complex {
+    ($ty:ty) => {
+        unsafe impl H5Type for Complex<$ty> {
+            fn type_descriptor() -> TypeDescriptor {
+                TypeDescriptor::Compound(CompoundType {
+                    fields: vec![
+                        CompoundField::typed::<$ty>("r", 0, 0),
+                        CompoundField::typed::<$ty>("i", mem::size_of::<$ty>(), 1),
+                    ],
+                    size: mem::size_of::<Complex<$ty>>(),
+                })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "complex")]
+impl_complex!(f32);
+#[cfg(feature = "complex")]
+impl_complex!(f64);
+
 #[cfg(test)]
 pub mod tests {
     use super::TypeDescriptor as TD;
@@ -508,4 +540,31 @@ pub mod tests {
         );
         assert_eq!(td.size(), 14);
     }
+
+    #[test]
+    #[cfg(feature = "complex")]
+    pub fn test_complex() {
+        use num_complex::Complex;
+
+        assert_eq!(
+            Complex::<f32>::type_descriptor(),
+            TD::Compound(CompoundType {
+                fields: vec![
+                    CompoundField::typed::<f32>("r", 0, 0),
+                    CompoundField::typed::<f32>("i", 4, 1),
+                ],
+                size: 8,
+            })
+        );
+        assert_eq!(
+            Complex::<f64>::type_descriptor(),
+            TD::Compound(CompoundType {
+                fields: vec![
+                    CompoundField::typed::<f64>("r", 0, 0),
+                    CompoundField::typed::<f64>("i", 8, 1),
+                ],
+                size: 16,
+            })
+        );
+    }
 }
@@ -14,3 +14,6 @@ pub use self::h5type::{
     CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor,
 };
 pub use self::string::{FixedAscii, FixedUnicode, StringError, VarLenAscii, VarLenUnicode};
+
+#[cfg(feature = "complex")]
+pub use num_complex::Complex;
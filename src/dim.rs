@@ -82,3 +82,31 @@ impl Dimension for Ix {
         vec![*self]
     }
 }
+
+macro_rules! impl_ndarray_dim {
+    ($($ndim:expr),*) => {$(
+        impl Dimension for ndarray::Dim<[Ix; $ndim]> {
+            #[inline]
+            fn ndim(&self) -> usize {
+                $ndim
+            }
+
+            #[inline]
+            fn dims(&self) -> Vec<Ix> {
+                ndarray::Dimension::slice(self).to_vec()
+            }
+        }
+    )*};
+}
+
+impl_ndarray_dim! { 0, 1, 2, 3, 4, 5, 6 }
+
+impl Dimension for ndarray::IxDyn {
+    fn ndim(&self) -> usize {
+        ndarray::Dimension::ndim(self)
+    }
+
+    fn dims(&self) -> Vec<Ix> {
+        ndarray::Dimension::slice(self).to_vec()
+    }
+}
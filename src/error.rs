@@ -11,10 +11,13 @@ use num_traits::{Bounded, Zero};
 use parking_lot::Mutex;
 
 use hdf5_sys::h5e::{
-    H5E_error2_t, H5Eclose_stack, H5Eget_current_stack, H5Eget_msg, H5Eprint2, H5Eset_auto2,
-    H5Ewalk2, H5E_DEFAULT, H5E_WALK_DOWNWARD,
+    H5E_auto2_t, H5E_error2_t, H5Eclose_stack, H5Eget_auto2, H5Eget_current_stack, H5Eget_msg,
+    H5Eset_auto2, H5Ewalk2, H5E_DEFAULT, H5E_WALK_DOWNWARD,
 };
 
+use crate::globals::{
+    H5E_ATOM, H5E_BADATOM, H5E_BADTYPE, H5E_DATATYPE, H5E_FILE, H5E_IO, H5E_NOTFOUND,
+};
 use crate::internal_prelude::*;
 
 #[derive(Clone)]
@@ -24,6 +27,8 @@ pub struct ErrorFrame {
     major: String,
     minor: String,
     description: String,
+    major_id: hid_t,
+    minor_id: hid_t,
 }
 
 impl ErrorFrame {
@@ -34,6 +39,8 @@ impl ErrorFrame {
             major: major.into(),
             minor: minor.into(),
             description: format!("{}(): {}", func, desc),
+            major_id: H5I_INVALID_HID,
+            minor_id: H5I_INVALID_HID,
         }
     }
 
@@ -60,12 +67,17 @@ impl Default for SilenceErrors {
     }
 }
 
-lazy_static! {
-    static ref ERROR_HANDLER: Mutex<RefCell<usize>> = Mutex::default();
+/// Nesting counter of `SilenceErrors` along with the error handler to restore once the
+/// outermost one is dropped (the client data pointer is stored as an integer).
+#[derive(Default)]
+struct SilenceState {
+    counter: usize,
+    func: H5E_auto2_t,
+    client_data: usize,
 }
 
-extern "C" fn default_error_handler(estack: hid_t, _cdata: *mut c_void) -> herr_t {
-    unsafe { H5Eprint2(estack, ptr::null_mut()) }
+lazy_static! {
+    static ref ERROR_HANDLER: Mutex<RefCell<SilenceState>> = Mutex::default();
 }
 
 impl SilenceErrors {
@@ -76,18 +88,24 @@ impl SilenceErrors {
 
     fn silence(on: bool) {
         let guard = ERROR_HANDLER.lock();
-        let counter = &mut *guard.borrow_mut();
+        let state = &mut *guard.borrow_mut();
         if on {
-            *counter += 1;
-            if *counter == 1 {
-                h5lock!(H5Eset_auto2(H5E_DEFAULT, None, ptr::null_mut()));
-            }
-        } else {
-            if *counter > 0 {
-                *counter -= 1;
+            state.counter += 1;
+            if state.counter == 1 {
+                // Save the current handler (which may have been disabled by a live
+                // `ThreadErrorGuard`) so that it can be restored instead of the default one.
+                let mut client_data: *mut c_void = ptr::null_mut();
+                h5lock!({
+                    H5Eget_auto2(H5E_DEFAULT, &mut state.func, &mut client_data);
+                    H5Eset_auto2(H5E_DEFAULT, None, ptr::null_mut());
+                });
+                state.client_data = client_data as usize;
             }
-            if *counter == 0 {
-                h5lock!(H5Eset_auto2(H5E_DEFAULT, Some(default_error_handler), ptr::null_mut()));
+        } else if state.counter > 0 {
+            state.counter -= 1;
+            if state.counter == 0 {
+                let client_data = state.client_data as *mut c_void;
+                h5lock!(H5Eset_auto2(H5E_DEFAULT, state.func, client_data));
             }
         }
     }
@@ -103,6 +121,43 @@ pub fn silence_errors() -> SilenceErrors {
     SilenceErrors::new()
 }
 
+/// Disables automatic printing of HDF5 errors while alive and restores the previous error
+/// handler when dropped (guards must be dropped in reverse order of creation).
+///
+/// With a thread-safe build of the library, the handler is a property of the default error
+/// stack of the calling thread, so other threads are unaffected; otherwise there's a single
+/// process-wide handler and the guard behaves like `silence_errors()` without the nesting
+/// counter shared between threads.
+#[must_use]
+pub struct ThreadErrorGuard {
+    func: H5E_auto2_t,
+    client_data: *mut c_void,
+}
+
+impl ThreadErrorGuard {
+    pub fn new() -> Self {
+        let mut func: H5E_auto2_t = None;
+        let mut client_data: *mut c_void = ptr::null_mut();
+        h5lock!({
+            H5Eget_auto2(H5E_DEFAULT, &mut func, &mut client_data);
+            H5Eset_auto2(H5E_DEFAULT, None, ptr::null_mut());
+        });
+        Self { func, client_data }
+    }
+}
+
+impl Default for ThreadErrorGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ThreadErrorGuard {
+    fn drop(&mut self) {
+        h5lock!(H5Eset_auto2(H5E_DEFAULT, self.func, self.client_data));
+    }
+}
+
 #[derive(Clone)]
 pub struct ErrorStack {
     frames: Vec<ErrorFrame>,
@@ -143,7 +198,10 @@ impl ErrorStack {
                     let (desc, func) = (string_from_cstr(e.desc), string_from_cstr(e.func_name));
                     let major = get_h5_str(|m, s| H5Eget_msg(e.maj_num, ptr::null_mut(), m, s))?;
                     let minor = get_h5_str(|m, s| H5Eget_msg(e.min_num, ptr::null_mut(), m, s))?;
-                    Ok(ErrorFrame::new(&desc, &func, &major, &minor))
+                    let mut frame = ErrorFrame::new(&desc, &func, &major, &minor);
+                    frame.major_id = e.maj_num;
+                    frame.minor_id = e.min_num;
+                    Ok(frame)
                 };
                 match closure(*err_desc) {
                     Ok(frame) => {
@@ -218,13 +276,42 @@ impl ErrorStack {
     pub fn detail(&self) -> Option<String> {
         self.top().and_then(ErrorFrame::detail)
     }
+
+    /// Wraps the stack into an error variant chosen by the error numbers of its frames,
+    /// starting from the innermost one (which is usually the most specific).
+    fn into_error(self) -> Error {
+        for frame in self.frames.iter().rev() {
+            let (major, minor) = (frame.major_id, frame.minor_id);
+            if minor == *H5E_NOTFOUND {
+                return Error::NotFound(self);
+            } else if major == *H5E_ATOM || minor == *H5E_BADATOM {
+                return Error::InvalidId(self);
+            } else if major == *H5E_DATATYPE || minor == *H5E_BADTYPE {
+                return Error::TypeMismatch(self);
+            } else if major == *H5E_IO || major == *H5E_FILE {
+                return Error::Io(self);
+            }
+        }
+        Error::HDF5(self)
+    }
 }
 
 /// The error type for HDF5-related functions.
+///
+/// Errors raised by the HDF5 library are classified by the error numbers found in the
+/// captured error stack; those that don't fall into any specific category are `HDF5`.
 #[derive(Clone)]
 pub enum Error {
     /// An error occurred in the C API of the HDF5 library. Full error stack is captured.
     HDF5(ErrorStack),
+    /// An invalid or already closed object identifier was passed to the library.
+    InvalidId(ErrorStack),
+    /// An object, link or attribute could not be found.
+    NotFound(ErrorStack),
+    /// A datatype operation failed, e.g. types are incompatible or can't be converted.
+    TypeMismatch(ErrorStack),
+    /// A file or low-level I/O operation failed.
+    Io(ErrorStack),
     /// A user error occurred in the high-level Rust API (e.g., invalid user input).
     Internal(String),
 }
@@ -237,15 +324,27 @@ impl Error {
     pub fn query() -> Option<Self> {
         match ErrorStack::query() {
             Err(err) => Some(err),
-            Ok(Some(stack)) => Some(Error::HDF5(stack)),
+            Ok(Some(stack)) => Some(stack.into_error()),
             Ok(None) => None,
         }
     }
 
+    /// Returns the HDF5 error stack, unless this is an internal error.
+    pub fn stack(&self) -> Option<&ErrorStack> {
+        match *self {
+            Error::Internal(_) => None,
+            Error::HDF5(ref stack)
+            | Error::InvalidId(ref stack)
+            | Error::NotFound(ref stack)
+            | Error::TypeMismatch(ref stack)
+            | Error::Io(ref stack) => Some(stack),
+        }
+    }
+
     pub fn description(&self) -> &str {
         match *self {
             Error::Internal(ref desc) => desc.as_ref(),
-            Error::HDF5(ref stack) => stack.description(),
+            _ => self.stack().map_or("unknown library error", ErrorStack::description),
         }
     }
 }
@@ -264,10 +363,7 @@ impl From<String> for Error {
 
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Error::Internal(ref desc) => f.write_str(desc),
-            Error::HDF5(ref stack) => f.write_str(stack.description()),
-        }
+        f.write_str(self.description())
     }
 }
 
@@ -310,7 +406,7 @@ pub mod tests {
     use crate::globals::H5P_ROOT;
     use crate::internal_prelude::*;
 
-    use super::ErrorStack;
+    use super::{Error, ErrorStack};
 
     #[test]
     pub fn test_error_stack() {
@@ -400,4 +496,118 @@ pub mod tests {
         let result2 = f2();
         assert!(result2.is_err());
     }
+
+    #[test]
+    pub fn test_error_kind() {
+        macro_rules! assert_kind {
+            ($err:expr, $pat:pat) => {
+                match $err {
+                    $pat => (),
+                    ref err => panic!("unexpected error: {:?}", err),
+                }
+            };
+        }
+
+        let _e = silence_errors();
+
+        let err = h5call!({
+            let plist_id = H5Pcreate(*H5P_ROOT);
+            H5Pclose(plist_id);
+            H5Pclose(plist_id)
+        })
+        .unwrap_err();
+        assert_kind!(err, Error::InvalidId(_));
+        assert_eq!(err.to_string(), "H5Pclose(): can't close: can't locate ID");
+
+        with_tmp_file(|file| {
+            let err = file.group("missing").unwrap_err();
+            assert_kind!(err, Error::NotFound(_));
+            assert!(err.stack().is_some());
+            assert_kind!(file.dataset("a/b"), Err(Error::NotFound(_)));
+        });
+
+        with_tmp_dir(|dir| {
+            let err = File::open(dir.join("missing.h5")).unwrap_err();
+            assert_kind!(err, Error::Io(_));
+        });
+
+        let err = Error::from("foo");
+        assert_kind!(err, Error::Internal(_));
+        assert!(err.stack().is_none());
+        assert_eq!(err.to_string(), "foo");
+    }
+
+    #[cfg(h5_have_threadsafe)]
+    fn is_silenced() -> bool {
+        use hdf5_sys::h5e::{H5E_auto2_t, H5Eget_auto2, H5E_DEFAULT};
+
+        let mut func: H5E_auto2_t = None;
+        h5lock!(H5Eget_auto2(H5E_DEFAULT, &mut func, std::ptr::null_mut()));
+        func.is_none()
+    }
+
+    #[test]
+    #[cfg(h5_have_threadsafe)]
+    pub fn test_thread_error_guard_nested_silence() {
+        use std::thread;
+
+        use hdf5_types::VarLenUnicode;
+
+        use super::ThreadErrorGuard;
+
+        // run on a fresh thread so that its error handler is not shared with other tests
+        thread::spawn(|| {
+            let guard = ThreadErrorGuard::new();
+            assert!(is_silenced());
+            // silences errors internally via `silence_errors()`
+            let dtype = Datatype::from_type::<VarLenUnicode>().unwrap();
+            assert_eq!(format!("{:?}", dtype), "<HDF5 datatype: variable-length UTF-8 string>");
+            drop(silence_errors());
+            assert!(is_silenced());
+            drop(guard);
+            assert!(!is_silenced());
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    #[cfg(h5_have_threadsafe)]
+    pub fn test_thread_error_guard() {
+        use std::sync::{Arc, Barrier};
+        use std::thread;
+
+        use super::ThreadErrorGuard;
+
+        let barrier = Arc::new(Barrier::new(2));
+        let threads: Vec<_> = (0..2)
+            .map(|i| {
+                let barrier = barrier.clone();
+                thread::spawn(move || {
+                    assert!(!is_silenced());
+                    if i == 0 {
+                        let guard = ThreadErrorGuard::new();
+                        assert!(is_silenced());
+                        let nested = ThreadErrorGuard::new();
+                        drop(nested);
+                        assert!(is_silenced());
+                        barrier.wait();
+                        barrier.wait();
+                        drop(guard);
+                    } else {
+                        barrier.wait();
+                        assert!(!is_silenced());
+                        let _guard = ThreadErrorGuard::new();
+                        assert!(is_silenced());
+                        barrier.wait();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        // each thread restores its own handler without touching the others
+        thread::spawn(|| assert!(!is_silenced())).join().unwrap();
+    }
 }
@@ -1,3 +1,5 @@
+use bitflags::bitflags;
+
 use crate::globals::H5P_DATASET_CREATE;
 use crate::internal_prelude::*;
 
@@ -15,14 +17,45 @@ use hdf5_sys::{
     },
 };
 
+bitflags! {
+    /// Capabilities of a filter available in the HDF5 library, see `filter_info()`.
+    pub struct FilterFlags: c_uint {
+        /// The filter can be used to write (encode) data.
+        const ENCODE_ENABLED = H5Z_FILTER_CONFIG_ENCODE_ENABLED;
+        /// The filter can be used to read (decode) data.
+        const DECODE_ENABLED = H5Z_FILTER_CONFIG_DECODE_ENABLED;
+    }
+}
+
+/// Returns `true` if the filter with the given identifier (e.g. `H5Z_FILTER_DEFLATE`) is
+/// available in the HDF5 library, either built in or registered at runtime.
+pub fn filter_available(filter_id: H5Z_filter_t) -> bool {
+    h5lock!(H5Zfilter_avail(filter_id) == 1)
+}
+
+/// Returns the encoding/decoding capabilities of the filter with the given identifier
+/// (empty if the filter is not available).
+pub fn filter_info(filter_id: H5Z_filter_t) -> FilterFlags {
+    let _e = silence_errors();
+    h5lock!({
+        if !filter_available(filter_id) {
+            return FilterFlags::empty();
+        }
+        let mut flags: c_uint = 0;
+        h5call!(H5Zget_filter_info(filter_id, &mut flags))
+            .map(|_| FilterFlags::from_bits_truncate(flags))
+            .unwrap_or_else(|_| FilterFlags::empty())
+    })
+}
+
 /// Returns `true` if gzip filter is available.
 pub fn gzip_available() -> bool {
-    h5lock!(H5Zfilter_avail(H5Z_FILTER_DEFLATE) == 1)
+    filter_available(H5Z_FILTER_DEFLATE)
 }
 
 /// Returns `true` if szip filter is available.
 pub fn szip_available() -> bool {
-    h5lock!(H5Zfilter_avail(H5Z_FILTER_SZIP) == 1)
+    filter_available(H5Z_FILTER_SZIP)
 }
 
 /// HDF5 filters and compression options.
@@ -235,18 +268,20 @@ impl Filters {
     }
 
     fn ensure_available(&self, name: &str, code: H5Z_filter_t) -> Result<()> {
-        ensure!(h5lock!(H5Zfilter_avail(code) == 1), "Filter not available: {}", name);
-
-        let flags: *mut c_uint = &mut 0;
-        h5try!(H5Zget_filter_info(code, flags));
+        ensure!(
+            filter_available(code),
+            "Filter not available: {} (not built into or registered with the HDF5 library)",
+            name
+        );
 
+        let flags = filter_info(code);
         ensure!(
-            unsafe { *flags & H5Z_FILTER_CONFIG_ENCODE_ENABLED != 0 },
+            flags.contains(FilterFlags::ENCODE_ENABLED),
             "Encoding is not enabled for filter: {}",
             name
         );
         ensure!(
-            unsafe { *flags & H5Z_FILTER_CONFIG_DECODE_ENABLED != 0 },
+            flags.contains(FilterFlags::DECODE_ENABLED),
             "Decoding is not enabled for filter: {}",
             name
         );
@@ -310,7 +345,9 @@ impl Filters {
 
 #[cfg(test)]
 pub mod tests {
-    use super::{gzip_available, szip_available};
+    use hdf5_sys::h5z::{H5Z_FILTER_DEFLATE, H5Z_FILTER_SZIP};
+
+    use super::{filter_available, filter_info, gzip_available, szip_available, FilterFlags};
     use crate::internal_prelude::*;
 
     fn make_filters<T: H5Type>(filters: &Filters) -> Result<Filters> {
@@ -357,6 +394,28 @@ pub mod tests {
         }
     }
 
+    #[test]
+    pub fn test_filter_available() {
+        assert_eq!(filter_available(H5Z_FILTER_DEFLATE), gzip_available());
+        assert_eq!(filter_available(H5Z_FILTER_SZIP), szip_available());
+        assert!(!filter_available(31999));
+        assert_eq!(filter_info(31999), FilterFlags::empty());
+
+        with_tmp_file(|file| {
+            let _e = silence_errors();
+            let builder = file.new_dataset::<u32>().gzip(6).clone();
+            if gzip_available() {
+                assert!(filter_info(H5Z_FILTER_DEFLATE).contains(FilterFlags::DECODE_ENABLED));
+                let ds = builder.create("foo", 10).unwrap();
+                assert_eq!(ds.filters().get_gzip(), Some(6));
+            } else {
+                assert_eq!(filter_info(H5Z_FILTER_DEFLATE), FilterFlags::empty());
+                assert_err!(builder.create("foo", 10), "Filter not available: gzip");
+                assert!(!file.link_exists("foo"));
+            }
+        })
+    }
+
     #[test]
     pub fn test_gzip() {
         let _e = silence_errors();
@@ -358,14 +358,16 @@ lazy_static! {
 #[cfg(test)]
 mod tests {
     use std::mem;
+    use std::os::raw::{c_float, c_longlong, c_short, c_ulong, c_ulonglong, c_ushort};
 
-    use hdf5_sys::{h5::haddr_t, h5i::H5I_INVALID_HID};
-
-    use super::{
-        H5E_DATASET, H5E_ERR_CLS, H5P_LST_LINK_ACCESS_ID, H5P_ROOT, H5R_DSET_REG_REF_BUF_SIZE,
-        H5R_OBJ_REF_BUF_SIZE, H5T_IEEE_F32BE, H5T_NATIVE_INT,
+    use hdf5_sys::{
+        h5::haddr_t,
+        h5i::H5I_INVALID_HID,
+        h5t::{H5T_class_t::*, H5Tget_class, H5Tget_size},
     };
 
+    use super::*;
+
     #[test]
     pub fn test_lazy_globals() {
         assert_ne!(*H5T_IEEE_F32BE, H5I_INVALID_HID);
@@ -380,4 +382,30 @@ mod tests {
         assert_eq!(*H5R_OBJ_REF_BUF_SIZE, mem::size_of::<haddr_t>());
         assert_eq!(*H5R_DSET_REG_REF_BUF_SIZE, mem::size_of::<haddr_t>() + 4);
     }
+
+    #[test]
+    pub fn test_native_types() {
+        let check = |id: hid_t, class, size| {
+            assert_ne!(id, H5I_INVALID_HID);
+            assert_eq!(h5lock!(H5Tget_class(id)), class);
+            assert_eq!(h5lock!(H5Tget_size(id)), size);
+        };
+        check(*H5T_NATIVE_SCHAR, H5T_INTEGER, 1);
+        check(*H5T_NATIVE_UCHAR, H5T_INTEGER, 1);
+        check(*H5T_NATIVE_SHORT, H5T_INTEGER, mem::size_of::<c_short>());
+        check(*H5T_NATIVE_USHORT, H5T_INTEGER, mem::size_of::<c_ushort>());
+        check(*H5T_NATIVE_INT, H5T_INTEGER, mem::size_of::<c_int>());
+        check(*H5T_NATIVE_UINT, H5T_INTEGER, mem::size_of::<c_uint>());
+        check(*H5T_NATIVE_LONG, H5T_INTEGER, mem::size_of::<c_long>());
+        check(*H5T_NATIVE_ULONG, H5T_INTEGER, mem::size_of::<c_ulong>());
+        check(*H5T_NATIVE_LLONG, H5T_INTEGER, mem::size_of::<c_longlong>());
+        check(*H5T_NATIVE_ULLONG, H5T_INTEGER, mem::size_of::<c_ulonglong>());
+        check(*H5T_NATIVE_FLOAT, H5T_FLOAT, mem::size_of::<c_float>());
+        check(*H5T_NATIVE_DOUBLE, H5T_FLOAT, mem::size_of::<c_double>());
+        check(*H5T_NATIVE_B8, H5T_BITFIELD, 1);
+        check(*H5T_NATIVE_B16, H5T_BITFIELD, 2);
+        check(*H5T_NATIVE_B32, H5T_BITFIELD, 4);
+        check(*H5T_NATIVE_B64, H5T_BITFIELD, 8);
+        check(*H5T_C_S1, H5T_STRING, 1);
+    }
 }
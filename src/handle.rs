@@ -115,6 +115,16 @@ impl Handle {
         is_valid_id(self.id())
     }
 
+    /// Returns `true` if both handles share the same underlying identifier slot.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.id, &other.id)
+    }
+
+    /// Returns a weak reference to the identifier which doesn't hold a reference count.
+    pub fn downgrade(&self) -> WeakHandle {
+        WeakHandle { id: self.id.clone() }
+    }
+
     pub fn decref_full(&self) {
         while self.is_valid_user_id() {
             self.decref();
@@ -122,6 +132,31 @@ impl Handle {
     }
 }
 
+/// Weak counterpart of `Handle` which doesn't keep the identifier open.
+#[derive(Clone)]
+pub struct WeakHandle {
+    id: Arc<RwLock<hid_t>>,
+}
+
+impl WeakHandle {
+    pub fn id(&self) -> hid_t {
+        *self.id.read()
+    }
+
+    /// Returns a strong handle (incrementing the reference count) if the identifier is
+    /// still valid, and `None` otherwise.
+    pub fn upgrade(&self) -> Option<Handle> {
+        h5lock!({
+            if is_valid_user_id(self.id()) {
+                H5Iinc_ref(self.id());
+                Some(Handle { id: self.id.clone() })
+            } else {
+                None
+            }
+        })
+    }
+}
+
 impl Clone for Handle {
     fn clone(&self) -> Self {
         h5lock!({
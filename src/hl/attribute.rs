@@ -0,0 +1,177 @@
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use hdf5_sys::h5a::{H5Acreate2, H5Aget_name};
+
+use crate::internal_prelude::*;
+
+/// Represents the HDF5 attribute object.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct Attribute(Handle);
+
+impl ObjectClass for Attribute {
+    const NAME: &'static str = "attribute";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_ATTR];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn short_repr(&self) -> Option<String> {
+        let ndim = self.ndim();
+        if ndim == 0 {
+            Some(format!("\"{}\": scalar", self.attr_name()))
+        } else {
+            Some(format!("\"{}\": {:?}", self.attr_name(), self.shape()))
+        }
+    }
+}
+
+impl Debug for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.debug_fmt(f)
+    }
+}
+
+impl Deref for Attribute {
+    type Target = Container;
+
+    fn deref(&self) -> &Container {
+        unsafe { self.transmute() }
+    }
+}
+
+impl Attribute {
+    /// Returns the name of the attribute (or empty string if the handle is invalid).
+    pub fn attr_name(&self) -> String {
+        h5lock!(get_h5_str(|m, s| H5Aget_name(self.id(), s, m)).unwrap_or_else(|_| "".into()))
+    }
+}
+
+#[derive(Clone)]
+pub struct AttributeBuilder<T> {
+    packed: bool,
+    parent: Result<Handle>,
+    phantom: PhantomData<T>,
+}
+
+impl<T: H5Type> AttributeBuilder<T> {
+    /// Create a new attribute builder and bind it to the parent location.
+    pub fn new(parent: &Location) -> Self {
+        h5lock!({
+            // Store the reference to the parent handle and try to increase its reference count.
+            let handle = Handle::try_new(parent.id());
+            if let Ok(ref handle) = handle {
+                handle.incref();
+            }
+
+            Self { packed: false, parent: handle, phantom: PhantomData }
+        })
+    }
+
+    pub fn packed(&mut self, packed: bool) -> &mut Self {
+        self.packed = packed;
+        self
+    }
+
+    /// Create the attribute and attach it to the parent location.
+    pub fn create<D: Dimension>(&self, name: &str, shape: D) -> Result<Attribute> {
+        let type_descriptor = if self.packed {
+            <T as H5Type>::type_descriptor().to_packed_repr()
+        } else {
+            <T as H5Type>::type_descriptor().to_c_repr()
+        };
+        h5lock!({
+            let datatype = Datatype::from_descriptor(&type_descriptor)?;
+            let parent = try_ref_clone!(self.parent);
+            let dataspace = Dataspace::try_new(&shape, false)?;
+            let name = to_cstring(name)?;
+            Attribute::from_id(h5try!(H5Acreate2(
+                parent.id(),
+                name.as_ptr(),
+                datatype.id(),
+                dataspace.id(),
+                H5P_DEFAULT,
+                H5P_DEFAULT
+            )))
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_create_attr() {
+        with_tmp_file(|file| {
+            let attr = file.new_attr::<u32>().create("foo", ()).unwrap();
+            assert!(attr.is_valid());
+            assert!(attr.is_scalar());
+            assert_eq!(attr.attr_name(), "foo");
+            attr.write_scalar(&42).unwrap();
+            assert_eq!(file.attr("foo").unwrap().read_scalar::<u32>().unwrap(), 42);
+            assert_err!(file.new_attr::<u32>().create("foo", ()), "unable to create attribute");
+            assert!(file.attr("bar").is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_debug() {
+        with_tmp_file(|file| {
+            let attr = file.new_attr::<u32>().create("foo", ()).unwrap();
+            assert_eq!(format!("{:?}", attr), "<HDF5 attribute: \"foo\": scalar>");
+            let attr = file.new_attr::<u32>().create("bar", (2, 3)).unwrap();
+            assert_eq!(format!("{:?}", attr), "<HDF5 attribute: \"bar\": [2, 3]>");
+        })
+    }
+
+    #[test]
+    pub fn test_attr_names_delete() {
+        with_tmp_file(|file| {
+            let group = file.create_group("a").unwrap();
+            assert!(group.attr_names().unwrap().is_empty());
+            group.new_attr::<u32>().create("foo", ()).unwrap();
+            group.new_attr::<f64>().create("bar", 3).unwrap();
+            assert_eq!(group.attr_names().unwrap(), vec!["bar", "foo"]);
+            assert!(group.has_attr("foo"));
+            assert!(group.has_attr("bar"));
+            assert!(!group.has_attr("baz"));
+
+            group.delete_attr("foo").unwrap();
+            assert!(!group.has_attr("foo"));
+            assert!(group.has_attr("bar"));
+            assert_eq!(group.attr_names().unwrap(), vec!["bar"]);
+            assert!(group.delete_attr("foo").is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_array_attr() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u8>().create("ds", 1).unwrap();
+            let attr = ds.new_attr::<f64>().create("foo", 3).unwrap();
+            attr.write_raw(&[1.5, 2.5, 3.5]).unwrap();
+
+            let attr = ds.attr("foo").unwrap();
+            assert_eq!(attr.shape(), vec![3]);
+            assert_eq!(attr.ndim(), 1);
+            assert!(attr.dtype().unwrap().is::<f64>());
+            assert_eq!(attr.read_raw::<f64>().unwrap(), vec![1.5, 2.5, 3.5]);
+            assert_eq!(attr.read_1d::<f64>().unwrap().to_vec(), vec![1.5, 2.5, 3.5]);
+            assert_err!(attr.read_scalar::<f64>(), "ndim mismatch");
+            assert_err!(attr.write_raw(&[1.0, 2.0]), "length mismatch when writing");
+
+            let attr = ds.new_attr::<i32>().create("bar", (2, 3)).unwrap();
+            attr.write(&ndarray::arr2(&[[1, 2, 3], [4, 5, 6]])).unwrap();
+            assert_eq!(attr.shape(), vec![2, 3]);
+            assert_eq!(attr.read_2d::<i32>().unwrap(), ndarray::arr2(&[[1, 2, 3], [4, 5, 6]]));
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::{self, Debug};
+use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
 
@@ -6,22 +7,28 @@ use ndarray::{Array, Array1, Array2, ArrayD, ArrayView, ArrayView1};
 use ndarray::{SliceInfo, SliceOrIndex};
 
 use hdf5_sys::h5a::{H5Aget_space, H5Aget_storage_size, H5Aget_type, H5Aread, H5Awrite};
-use hdf5_sys::h5d::{H5Dget_space, H5Dget_storage_size, H5Dget_type, H5Dread, H5Dwrite};
+use hdf5_sys::h5d::{
+    H5Dget_space, H5Dget_storage_size, H5Dget_type, H5Dread, H5Dvlen_reclaim, H5Dwrite,
+};
 
+use crate::hl::plist::dataset_transfer::DatasetTransfer;
 use crate::internal_prelude::*;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Reader<'a> {
     obj: &'a Container,
     conv: Conversion,
+    dxpl: Option<DatasetTransfer>,
+    reinterpret: bool,
 }
 
 impl<'a> Reader<'a> {
     /// Creates a reader for a dataset/attribute.
     ///
-    /// Any conversions (including hard/soft) are allowed by default.
+    /// Any conversions (including hard/soft) are allowed by default, except for reading
+    /// integers into a type of different signedness that can't hold all stored values.
     pub fn new(obj: &'a Container) -> Self {
-        Self { obj, conv: Conversion::Soft }
+        Self { obj, conv: Conversion::Soft, dxpl: None, reinterpret: false }
     }
 
     /// Set maximum allowed conversion level.
@@ -36,12 +43,34 @@ impl<'a> Reader<'a> {
         self
     }
 
+    /// Allow reading integers into a type of different signedness (e.g. unsigned
+    /// integers into `i64`); values out of range are then clamped by the library.
+    pub fn reinterpret(mut self, reinterpret: bool) -> Self {
+        self.reinterpret = reinterpret;
+        self
+    }
+
+    /// Set the dataset transfer property list (ignored for attributes).
+    pub fn transfer_plist(mut self, dxpl: &DatasetTransfer) -> Self {
+        self.dxpl = Some(dxpl.clone());
+        self
+    }
+
+    /// A short alias for `transfer_plist()`.
+    pub fn dxpl(self, dxpl: &DatasetTransfer) -> Self {
+        self.transfer_plist(dxpl)
+    }
+
     fn read_into_buf<T: H5Type>(
         &self, buf: *mut T, fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
     ) -> Result<()> {
         let file_dtype = self.obj.dtype()?;
         let mem_dtype = Datatype::from_type::<T>()?;
+        mem_dtype.assert_layout_matches::<T>()?;
         file_dtype.ensure_convertible(&mem_dtype, self.conv)?;
+        if !self.reinterpret {
+            file_dtype.ensure_sign_compatible(&mem_dtype)?;
+        }
         let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
 
         let fspace_id = fspace.map_or(H5S_ALL, |f| f.id());
@@ -50,7 +79,8 @@ impl<'a> Reader<'a> {
         if self.obj.is_attr() {
             h5try!(H5Aread(obj_id, tp_id, buf as *mut _));
         } else {
-            h5try!(H5Dread(obj_id, tp_id, mspace_id, fspace_id, H5P_DEFAULT, buf as *mut _));
+            let dxpl_id = self.dxpl.as_ref().map_or(H5P_DEFAULT, |p| p.id());
+            h5try!(H5Dread(obj_id, tp_id, mspace_id, fspace_id, dxpl_id, buf as *mut _));
         }
         Ok(())
     }
@@ -100,6 +130,7 @@ impl<'a> Reader<'a> {
         } else {
             let fspace = self.obj.space()?;
             let out_shape = fspace.select_slice(slice)?;
+            ensure!(fspace.selection_valid(), "selection is out of dataspace bounds");
 
             // Remove dimensions from out_shape that were SliceOrIndex::Index in the slice
             let reduced_shape: Vec<_> = slice_s
@@ -163,6 +194,82 @@ impl<'a> Reader<'a> {
         self.read_into_buf(vec.as_mut_ptr(), None, None).map(|_| vec)
     }
 
+    /// Reads a dataset/attribute of 32-bit Unicode code points into a vector of characters
+    /// in memory order, failing if any of the values is not a valid Unicode scalar value.
+    ///
+    /// Characters can be written as code points, e.g. via `write_from_iter()` with
+    /// `u32::from` applied to each of them.
+    pub fn read_chars(&self) -> Result<Vec<char>> {
+        self.read_raw::<u32>()?
+            .into_iter()
+            .map(|code| {
+                std::char::from_u32(code)
+                    .ok_or_else(|| format!("invalid Unicode scalar value: {:#x}", code).into())
+            })
+            .collect()
+    }
+
+    /// Reads a dataset/attribute into a caller-provided buffer in memory order.
+    ///
+    /// The length of the buffer must match the number of elements in the dataset/attribute.
+    /// The buffer is overwritten without dropping its previous contents, hence `T: Copy`
+    /// (use `read_raw()` for types owning heap memory such as variable-length types).
+    pub fn read_into<T: H5Type + Copy>(&self, buf: &mut [T]) -> Result<()> {
+        let size = self.obj.space()?.size();
+        ensure!(
+            buf.len() == size,
+            "length mismatch when reading: buffer = {}, source = {}",
+            buf.len(),
+            size
+        );
+        self.read_into_buf(buf.as_mut_ptr(), None, None)
+    }
+
+    /// Reads the given `slice` of the dataset into a caller-provided buffer in memory order.
+    ///
+    /// The length of the buffer must match the number of elements in the slice; as with
+    /// `read_into()`, the previous contents of the buffer are not dropped.
+    pub fn read_slice_into<T, S>(&self, slice: S, buf: &mut [T]) -> Result<()>
+    where
+        T: H5Type + Copy,
+        S: AsRef<[SliceOrIndex]>,
+    {
+        ensure!(!self.obj.is_attr(), "slicing cannot be used on attribute datasets");
+        let fspace = self.obj.space()?;
+        let slice: &[SliceOrIndex] = slice.as_ref();
+        ensure!(
+            fspace.ndim() == slice.len(),
+            "slice dimension mismatch: dataset has {} dims, slice has {} dims",
+            fspace.ndim(),
+            slice.len()
+        );
+        let out_shape = fspace.select_slice(slice)?;
+        ensure!(fspace.selection_valid(), "selection is out of dataspace bounds");
+        let size = out_shape.size();
+        ensure!(
+            buf.len() == size,
+            "length mismatch when reading: buffer = {}, source = {}",
+            buf.len(),
+            size
+        );
+        let mspace = Dataspace::try_new(&out_shape, false)?;
+        self.read_into_buf(buf.as_mut_ptr(), Some(&fspace), Some(&mspace))
+    }
+
+    /// Reads the elements selected in `fspace` (a dataspace with the extent of the dataset,
+    /// e.g. a copy of `space()` with a selection applied) in selection order.
+    pub fn read_selection<T: H5Type>(&self, fspace: &Dataspace) -> Result<Vec<T>> {
+        ensure!(!self.obj.is_attr(), "selections cannot be used on attribute datasets");
+        ensure!(fspace.selection_valid(), "selection is out of dataspace bounds");
+        let size = fspace.selection_size();
+        let mspace = Dataspace::try_new(size, false)?;
+        let mut vec = Vec::with_capacity(size);
+        unsafe {
+            vec.set_len(size);
+        }
+        self.read_into_buf(vec.as_mut_ptr(), Some(fspace), Some(&mspace)).map(|_| vec)
+    }
+
     /// Reads a dataset/attribute into a 1-dimensional array.
     ///
     /// The dataset/attribute must be 1-dimensional.
@@ -202,6 +309,31 @@ impl<'a> Reader<'a> {
         self.read()
     }
 
+    /// Returns an iterator reading a dataset in blocks of at most `rows_per_chunk` rows
+    /// along the first axis (the last block may be smaller). Each block is returned as a
+    /// vector in memory order; the dataset is never loaded into memory as a whole.
+    pub fn row_chunks<T: H5Type>(&self, rows_per_chunk: usize) -> Result<RowChunks<'a, T>> {
+        ensure!(!self.obj.is_attr(), "row chunks cannot be read from attributes");
+        ensure!(rows_per_chunk > 0, "number of rows per chunk must be positive");
+        let shape = self.obj.get_shape()?;
+        ensure!(shape.ndim() > 0, "row chunks cannot be read from scalar datasets");
+        Ok(RowChunks { reader: self.clone(), shape, rows_per_chunk, pos: 0, phantom: PhantomData })
+    }
+
+    /// Reads a dataset/attribute containing variable-length data into a buffer whose
+    /// HDF5-allocated memory is reclaimed via `H5Dvlen_reclaim` when it is dropped.
+    pub fn read_vlen<T: H5Type>(&self) -> Result<VlenBuffer<T>> {
+        let space = self.obj.space()?;
+        let size = space.size();
+        let mut data: Vec<mem::ManuallyDrop<T>> = Vec::with_capacity(size);
+        unsafe {
+            data.set_len(size);
+        }
+        let dtype = Datatype::from_type::<T>()?;
+        self.read_into_buf(data.as_mut_ptr() as *mut T, None, None)?;
+        Ok(VlenBuffer { data, dtype, space })
+    }
+
     /// Reads a scalar dataset/attribute.
     pub fn read_scalar<T: H5Type>(&self) -> Result<T> {
         let obj_ndim = self.obj.get_shape()?.ndim();
@@ -211,10 +343,104 @@ impl<'a> Reader<'a> {
     }
 }
 
+/// Iterator over blocks of rows of a dataset, created via `Reader::row_chunks()`.
+#[derive(Debug)]
+pub struct RowChunks<'a, T> {
+    reader: Reader<'a>,
+    shape: Vec<Ix>,
+    rows_per_chunk: usize,
+    pos: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: H5Type> RowChunks<'a, T> {
+    fn read_rows(&self, start: usize, end: usize) -> Result<Vec<T>> {
+        let mut slice =
+            vec![SliceOrIndex::Slice { start: 0, end: None, step: 1 }; self.shape.len()];
+        slice[0] = SliceOrIndex::Slice { start: start as _, end: Some(end as _), step: 1 };
+
+        let fspace = self.reader.obj.space()?;
+        let out_shape = fspace.select_slice(&slice)?;
+        let mspace = Dataspace::try_new(&out_shape, false)?;
+        let size = out_shape.size();
+        let mut vec = Vec::with_capacity(size);
+        unsafe {
+            vec.set_len(size);
+        }
+        self.reader.read_into_buf(vec.as_mut_ptr(), Some(&fspace), Some(&mspace))?;
+        Ok(vec)
+    }
+}
+
+impl<'a, T: H5Type> Iterator for RowChunks<'a, T> {
+    type Item = Result<Vec<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let nrows = self.shape[0];
+        if self.pos >= nrows {
+            return None;
+        }
+        let start = self.pos;
+        let end = (start + self.rows_per_chunk).min(nrows);
+        self.pos = end;
+        Some(self.read_rows(start, end))
+    }
+}
+
+/// Buffer holding variable-length data read via `Reader::read_vlen()`.
+///
+/// The elements are owned by HDF5 and are released with `H5Dvlen_reclaim` exactly once
+/// when the buffer is dropped; use `to_vec()` to get owned copies that outlive it.
+pub struct VlenBuffer<T> {
+    data: Vec<mem::ManuallyDrop<T>>,
+    dtype: Datatype,
+    space: Dataspace,
+}
+
+impl<T> VlenBuffer<T> {
+    /// Returns the number of elements in the buffer.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns true if the buffer contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns a view of the elements, valid for the lifetime of the buffer.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { &*(self.data.as_slice() as *const [mem::ManuallyDrop<T>] as *const [T]) }
+    }
+
+    /// Copies the elements into a vector of owned values.
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.as_slice().to_vec()
+    }
+}
+
+impl<T: Debug> Debug for VlenBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T> Drop for VlenBuffer<T> {
+    fn drop(&mut self) {
+        let (tp_id, space_id) = (self.dtype.id(), self.space.id());
+        let buf = self.data.as_mut_ptr() as *mut _;
+        h5lock!(H5Dvlen_reclaim(tp_id, space_id, H5P_DEFAULT, buf));
+    }
+}
+
 #[derive(Debug)]
 pub struct Writer<'a> {
     obj: &'a Container,
     conv: Conversion,
+    dxpl: Option<DatasetTransfer>,
 }
 
 impl<'a> Writer<'a> {
@@ -222,7 +448,7 @@ impl<'a> Writer<'a> {
     ///
     /// Any conversions (including hard/soft) are allowed by default.
     pub fn new(obj: &'a Container) -> Self {
-        Self { obj, conv: Conversion::Soft }
+        Self { obj, conv: Conversion::Soft, dxpl: None }
     }
 
     /// Set maximum allowed conversion level.
@@ -237,11 +463,23 @@ impl<'a> Writer<'a> {
         self
     }
 
+    /// Set the dataset transfer property list (ignored for attributes).
+    pub fn transfer_plist(mut self, dxpl: &DatasetTransfer) -> Self {
+        self.dxpl = Some(dxpl.clone());
+        self
+    }
+
+    /// A short alias for `transfer_plist()`.
+    pub fn dxpl(self, dxpl: &DatasetTransfer) -> Self {
+        self.transfer_plist(dxpl)
+    }
+
     fn write_from_buf<T: H5Type>(
         &self, buf: *const T, fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
     ) -> Result<()> {
         let file_dtype = self.obj.dtype()?;
         let mem_dtype = Datatype::from_type::<T>()?;
+        mem_dtype.assert_layout_matches::<T>()?;
         mem_dtype.ensure_convertible(&file_dtype, self.conv)?;
         let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
 
@@ -251,7 +489,8 @@ impl<'a> Writer<'a> {
         if self.obj.is_attr() {
             h5try!(H5Awrite(obj_id, tp_id, buf as *const _));
         } else {
-            h5try!(H5Dwrite(obj_id, tp_id, mspace_id, fspace_id, H5P_DEFAULT, buf as *const _));
+            let dxpl_id = self.dxpl.as_ref().map_or(H5P_DEFAULT, |p| p.id());
+            h5try!(H5Dwrite(obj_id, tp_id, mspace_id, fspace_id, dxpl_id, buf as *const _));
         }
         Ok(())
     }
@@ -290,6 +529,7 @@ impl<'a> Writer<'a> {
         } else {
             let fspace = self.obj.space()?;
             let slice_shape = fspace.select_slice(slice)?;
+            ensure!(fspace.selection_valid(), "selection is out of dataspace bounds");
 
             let view = arr.into();
             let data_shape = view.shape();
@@ -377,12 +617,68 @@ impl<'a> Writer<'a> {
         self.write_from_buf(view.as_ptr(), None, None)
     }
 
+    /// Writes the elements yielded by an iterator into a dataset/attribute in memory order.
+    ///
+    /// The length of the iterator must match the number of elements in the destination
+    /// dataset/attribute; it is checked before any elements are consumed.
+    pub fn write_from_iter<T, I>(&self, iter: I) -> Result<()>
+    where
+        T: H5Type,
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let iter = iter.into_iter();
+        let src = iter.len();
+        let dst = self.obj.get_shape()?.size();
+        if src != dst {
+            fail!("length mismatch when writing: memory = {:?}, destination = {:?}", src, dst);
+        }
+        let mut buf = Vec::with_capacity(src);
+        buf.extend(iter.take(src));
+        ensure!(buf.len() == src, "iterator yielded fewer elements than it reported");
+        self.write_from_buf(buf.as_ptr(), None, None)
+    }
+
     /// Writes a scalar dataset/attribute.
     pub fn write_scalar<T: H5Type>(&self, val: &T) -> Result<()> {
         let ndim = self.obj.get_shape()?.ndim();
         ensure!(ndim == 0, "ndim mismatch: expected scalar, got {}", ndim);
         self.write_from_buf(val as *const _, None, None)
     }
+
+    /// Sets every element of a dataset/attribute to `value`.
+    ///
+    /// Datasets are written in blocks of rows along the first axis through a buffer of
+    /// about 1 MiB (or a single row, if larger), rather than a buffer of the full size.
+    pub fn fill<T: H5Type + Clone>(&self, value: &T) -> Result<()> {
+        const BUFFER_SIZE: usize = 1024 * 1024;
+
+        let shape = self.obj.get_shape()?;
+        let size = shape.size();
+        if shape.ndim() == 0 {
+            return self.write_from_buf(value as *const _, None, None);
+        } else if size == 0 {
+            return Ok(());
+        } else if self.obj.is_attr() {
+            let buf = vec![value.clone(); size];
+            return self.write_from_buf(buf.as_ptr(), None, None);
+        }
+
+        let (nrows, row_size) = (shape[0], size / shape[0]);
+        let rows_per_block = (BUFFER_SIZE / (row_size * mem::size_of::<T>()).max(1)).max(1);
+        let rows_per_block = rows_per_block.min(nrows);
+        let buf = vec![value.clone(); rows_per_block * row_size];
+        let mut slice = vec![SliceOrIndex::Slice { start: 0, end: None, step: 1 }; shape.len()];
+        for start in (0..nrows).step_by(rows_per_block) {
+            let end = (start + rows_per_block).min(nrows);
+            slice[0] = SliceOrIndex::Slice { start: start as _, end: Some(end as _), step: 1 };
+            let fspace = self.obj.space()?;
+            let out_shape = fspace.select_slice(&slice)?;
+            let mspace = Dataspace::try_new(&out_shape, false)?;
+            self.write_from_buf(buf.as_ptr(), Some(&fspace), Some(&mspace))?;
+        }
+        Ok(())
+    }
 }
 
 #[repr(transparent)]
@@ -499,6 +795,35 @@ impl Container {
         self.as_reader().read_raw()
     }
 
+    /// Reads a dataset/attribute of 32-bit Unicode code points into a vector of characters
+    /// (see `Reader::read_chars()`).
+    pub fn read_chars(&self) -> Result<Vec<char>> {
+        self.as_reader().read_chars()
+    }
+
+    /// Reads a dataset/attribute into a caller-provided buffer in memory order.
+    ///
+    /// The length of the buffer must match the number of elements in the dataset/attribute.
+    pub fn read_into<T: H5Type + Copy>(&self, buf: &mut [T]) -> Result<()> {
+        self.as_reader().read_into(buf)
+    }
+
+    /// Reads the given `slice` of the dataset into a caller-provided buffer in memory order
+    /// (see `Reader::read_slice_into()`).
+    pub fn read_slice_into<T, S>(&self, slice: S, buf: &mut [T]) -> Result<()>
+    where
+        T: H5Type + Copy,
+        S: AsRef<[SliceOrIndex]>,
+    {
+        self.as_reader().read_slice_into(slice, buf)
+    }
+
+    /// Reads the elements selected in `fspace` in selection order
+    /// (see `Reader::read_selection()`).
+    pub fn read_selection<T: H5Type>(&self, fspace: &Dataspace) -> Result<Vec<T>> {
+        self.as_reader().read_selection(fspace)
+    }
+
     /// Reads a dataset/attribute into a 1-dimensional array.
     ///
     /// The dataset/attribute must be 1-dimensional.
@@ -552,6 +877,18 @@ impl Container {
         self.as_reader().read_slice(slice)
     }
 
+    /// Returns an iterator reading a dataset in blocks of at most `rows_per_chunk` rows
+    /// along the first axis (see `Reader::row_chunks()`).
+    pub fn row_chunks<T: H5Type>(&self, rows_per_chunk: usize) -> Result<RowChunks<T>> {
+        self.as_reader().row_chunks(rows_per_chunk)
+    }
+
+    /// Reads variable-length data into a buffer that reclaims HDF5-allocated memory
+    /// on drop (see `Reader::read_vlen()`).
+    pub fn read_vlen<T: H5Type>(&self) -> Result<VlenBuffer<T>> {
+        self.as_reader().read_vlen()
+    }
+
     /// Reads a scalar dataset/attribute.
     pub fn read_scalar<T: H5Type>(&self) -> Result<T> {
         self.as_reader().read_scalar()
@@ -583,6 +920,22 @@ impl Container {
         self.as_writer().write_raw(arr)
     }
 
+    /// Writes the elements yielded by an iterator into a dataset/attribute in memory order
+    /// (see `Writer::write_from_iter()`).
+    pub fn write_from_iter<T, I>(&self, iter: I) -> Result<()>
+    where
+        T: H5Type,
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        self.as_writer().write_from_iter(iter)
+    }
+
+    /// Sets every element of a dataset/attribute to `value` (see `Writer::fill()`).
+    pub fn fill<T: H5Type + Clone>(&self, value: &T) -> Result<()> {
+        self.as_writer().fill(value)
+    }
+
     /// Writes all data from the array `arr` into the given `slice` of the target dataset.
     /// The shape of `arr` must match the shape the set of elements included in the slice.
     /// If the array has a fixed number of dimensions, it must match the dimensionality of
@@ -1,4 +1,5 @@
 use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
 use std::mem;
 use std::ops::Deref;
 
@@ -7,17 +8,31 @@ use num_integer::div_floor;
 use hdf5_sys::{
     h5::HADDR_UNDEF,
     h5d::{
-        H5D_fill_value_t, H5D_layout_t, H5Dcreate2, H5Dcreate_anon, H5Dget_create_plist,
-        H5Dget_offset, H5Dset_extent, H5D_FILL_TIME_ALLOC,
+        H5D_alloc_time_t, H5D_fill_value_t, H5D_layout_t, H5Dcreate2, H5Dcreate_anon,
+        H5Dget_create_plist, H5Dget_offset, H5Dread, H5Dset_extent, H5Dvlen_get_buf_size, H5Dwrite,
+        H5D_FILL_TIME_ALLOC,
     },
+    h5o::H5Ocopy,
     h5p::{
-        H5Pcreate, H5Pfill_value_defined, H5Pget_chunk, H5Pget_fill_value, H5Pget_layout,
-        H5Pget_obj_track_times, H5Pset_chunk, H5Pset_create_intermediate_group, H5Pset_fill_time,
-        H5Pset_fill_value, H5Pset_obj_track_times,
+        H5Pcreate, H5Pfill_value_defined, H5Pget_alloc_time, H5Pget_chunk, H5Pget_external,
+        H5Pget_external_count, H5Pget_fill_value, H5Pget_layout, H5Pget_obj_track_times,
+        H5Pset_alloc_time, H5Pset_chunk, H5Pset_create_intermediate_group, H5Pset_external,
+        H5Pset_fill_time, H5Pset_fill_value, H5Pset_obj_track_times,
     },
+    h5t::H5Tget_member_index,
 };
 
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::h5p::H5Pset_virtual;
+
+#[cfg(hdf5_1_10_5)]
+use hdf5_sys::h5d::{H5Dget_chunk_info, H5Dget_num_chunks};
+#[cfg(hdf5_1_10_3)]
+use hdf5_sys::h5d::{H5Dget_chunk_storage_size, H5Dread_chunk, H5Dwrite_chunk};
+
 use crate::globals::H5P_LINK_CREATE;
+use crate::hl::group::make_lcpl;
+use crate::hl::plist::dataset_transfer::DatasetTransfer;
 use crate::internal_prelude::*;
 
 /// Represents the HDF5 dataset object.
@@ -54,6 +69,20 @@ impl Deref for Dataset {
     }
 }
 
+impl PartialEq for Dataset {
+    fn eq(&self, other: &Self) -> bool {
+        <Location as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for Dataset {}
+
+impl Hash for Dataset {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        <Location as Hash>::hash(self, state)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Chunk {
     None,
@@ -62,6 +91,57 @@ pub enum Chunk {
     Manual(Vec<Ix>),
 }
 
+/// Time at which the storage space of a dataset gets allocated in the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AllocTime {
+    /// The library default for the dataset layout (late for contiguous, incremental for
+    /// chunked and early for compact datasets).
+    #[default]
+    Default,
+    /// All space is allocated when the dataset is created.
+    Early,
+    /// Space for each chunk is allocated when data is first written to it.
+    Incremental,
+    /// All space is allocated when data is first written to the dataset.
+    Late,
+}
+
+impl From<H5D_alloc_time_t> for AllocTime {
+    fn from(alloc_time: H5D_alloc_time_t) -> Self {
+        match alloc_time {
+            H5D_alloc_time_t::H5D_ALLOC_TIME_EARLY => AllocTime::Early,
+            H5D_alloc_time_t::H5D_ALLOC_TIME_INCR => AllocTime::Incremental,
+            H5D_alloc_time_t::H5D_ALLOC_TIME_LATE => AllocTime::Late,
+            _ => AllocTime::Default,
+        }
+    }
+}
+
+impl From<AllocTime> for H5D_alloc_time_t {
+    fn from(alloc_time: AllocTime) -> Self {
+        match alloc_time {
+            AllocTime::Early => H5D_alloc_time_t::H5D_ALLOC_TIME_EARLY,
+            AllocTime::Incremental => H5D_alloc_time_t::H5D_ALLOC_TIME_INCR,
+            AllocTime::Late => H5D_alloc_time_t::H5D_ALLOC_TIME_LATE,
+            AllocTime::Default => H5D_alloc_time_t::H5D_ALLOC_TIME_DEFAULT,
+        }
+    }
+}
+
+/// Location and storage details of a single allocated chunk of a dataset.
+#[cfg(hdf5_1_10_5)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChunkInfo {
+    /// Logical position of the chunk's first element in the dataset.
+    pub offset: Vec<Ix>,
+    /// Mask of the filters that were skipped when the chunk was written.
+    pub filter_mask: u32,
+    /// Byte address of the chunk in the file.
+    pub addr: u64,
+    /// Size of the chunk in the file, in bytes.
+    pub size: u64,
+}
+
 impl Dataset {
     /// Returns whether this dataset is resizable along some axis.
     pub fn is_resizable(&self) -> bool {
@@ -96,6 +176,111 @@ impl Dataset {
         })
     }
 
+    /// Validates that `offset` is the logical position of a chunk's origin and converts it
+    /// to the form expected by the direct chunk I/O functions.
+    #[cfg(hdf5_1_10_3)]
+    fn chunk_offset(&self, offset: &[Ix]) -> Result<Vec<hsize_t>> {
+        let chunks = match self.chunks() {
+            Some(chunks) => chunks,
+            None => fail!("direct chunk I/O requires a chunked dataset"),
+        };
+        ensure!(
+            offset.len() == chunks.len(),
+            "chunk offset ndim mismatch: expected {}, got {}",
+            chunks.len(),
+            offset.len()
+        );
+        for (i, (&o, &c)) in offset.iter().zip(chunks.iter()).enumerate() {
+            ensure!(
+                o % c == 0,
+                "chunk offset is not aligned: {} is not a multiple of chunk size {} in dimension {}",
+                o,
+                c,
+                i
+            );
+        }
+        Ok(offset.iter().map(|&o| o as _).collect())
+    }
+
+    /// Reads the raw (still filtered, e.g. compressed) bytes of the chunk that starts at
+    /// `offset`, bypassing the filter pipeline and datatype conversion.
+    ///
+    /// Returns the filter mask stored with the chunk together with its bytes.
+    #[cfg(hdf5_1_10_3)]
+    pub fn read_chunk(&self, offset: &[Ix]) -> Result<(u32, Vec<u8>)> {
+        let offset = self.chunk_offset(offset)?;
+        h5lock!({
+            let mut size: hsize_t = 0;
+            h5try!(H5Dget_chunk_storage_size(self.id(), offset.as_ptr(), &mut size));
+            let mut buf = vec![0_u8; size as _];
+            let mut filter_mask: u32 = 0;
+            h5try!(H5Dread_chunk(
+                self.id(),
+                H5P_DEFAULT,
+                offset.as_ptr(),
+                &mut filter_mask,
+                buf.as_mut_ptr() as *mut _
+            ));
+            Ok((filter_mask, buf))
+        })
+    }
+
+    /// Writes already-filtered bytes directly as the chunk that starts at `offset`, bypassing
+    /// the filter pipeline and datatype conversion.
+    ///
+    /// Each bit set in `filter_mask` marks a filter of the pipeline that was *not* applied
+    /// to `data` (so `0` means all filters were applied).
+    #[cfg(hdf5_1_10_3)]
+    pub fn write_chunk(&self, offset: &[Ix], filter_mask: u32, data: &[u8]) -> Result<()> {
+        let offset = self.chunk_offset(offset)?;
+        h5try!(H5Dwrite_chunk(
+            self.id(),
+            H5P_DEFAULT,
+            filter_mask,
+            offset.as_ptr(),
+            data.len() as _,
+            data.as_ptr() as *const _
+        ));
+        Ok(())
+    }
+
+    /// Returns the number of chunks that have been allocated in the file for this dataset.
+    #[cfg(hdf5_1_10_5)]
+    pub fn num_chunks(&self) -> Result<u64> {
+        h5lock!({
+            let space = self.space()?;
+            let mut n: hsize_t = 0;
+            h5try!(H5Dget_num_chunks(self.id(), space.id(), &mut n));
+            Ok(n as _)
+        })
+    }
+
+    /// Returns the storage details of the allocated chunk with the given index, which must
+    /// be less than `num_chunks()`.
+    #[cfg(hdf5_1_10_5)]
+    pub fn chunk_info(&self, index: u64) -> Result<ChunkInfo> {
+        h5lock!({
+            let space = self.space()?;
+            let mut offset: Vec<hsize_t> = vec![0; space.ndim()];
+            let (mut filter_mask, mut addr, mut size) = (0, 0, 0);
+            h5try!(H5Dget_chunk_info(
+                self.id(),
+                space.id(),
+                index as _,
+                offset.as_mut_ptr(),
+                &mut filter_mask,
+                &mut addr,
+                &mut size
+            ));
+            Ok(ChunkInfo {
+                offset: offset.iter().map(|&x| x as _).collect(),
+                filter_mask: filter_mask as _,
+                addr: addr as _,
+                size: size as _,
+            })
+        })
+    }
+
     /// Returns the filters used to create the dataset.
     pub fn filters(&self) -> Filters {
         h5lock!({
@@ -105,6 +290,74 @@ impl Dataset {
         .unwrap_or_else(|_: crate::error::Error| Filters::default())
     }
 
+    /// Returns the external files storing the raw data of the dataset as a list of
+    /// `(path, offset, size)` segments (empty if the dataset has no external storage).
+    pub fn external_files(&self) -> Result<Vec<(String, u64, u64)>> {
+        h5lock!({
+            let dcpl = PropertyList::from_id(h5try!(H5Dget_create_plist(self.id())))?;
+            let count = h5try!(H5Pget_external_count(dcpl.id()));
+            let mut files = Vec::with_capacity(count as _);
+            for idx in 0..count {
+                let mut name: Vec<c_char> = vec![0; 4096];
+                let (mut offset, mut size) = (0, 0);
+                h5try!(H5Pget_external(
+                    dcpl.id(),
+                    idx as _,
+                    name.len() as _,
+                    name.as_mut_ptr(),
+                    &mut offset,
+                    &mut size
+                ));
+                let len = name.len();
+                files.push((string_from_fixed_bytes(&name, len), offset as _, size as _));
+            }
+            Ok(files)
+        })
+    }
+
+    /// Copies the dataset along with its attributes to `name` within `dst` (which may
+    /// belong to a different file) and opens the copy.
+    ///
+    /// Fails (removing the copy) if the filter pipeline of the copy doesn't match the one of
+    /// this dataset.
+    pub fn copy_to(&self, dst: &Group, name: &str) -> Result<Self> {
+        h5lock!({
+            let lcpl = make_lcpl()?;
+            let (src, c_name) = (to_cstring(".")?, to_cstring(name)?);
+            h5try!(H5Ocopy(
+                self.id(),
+                src.as_ptr(),
+                dst.id(),
+                c_name.as_ptr(),
+                H5P_DEFAULT,
+                lcpl.id()
+            ));
+            let copy = dst.dataset(name)?;
+            let (filters, copy_filters) = (self.filters(), copy.filters());
+            if copy_filters != filters {
+                // don't leave the mismatching copy behind
+                drop(copy);
+                dst.unlink(name)?;
+                fail!(
+                    "filter pipeline of the copy doesn't match: {:?} != {:?}",
+                    copy_filters,
+                    filters
+                );
+            }
+            Ok(copy)
+        })
+    }
+
+    /// Returns the time at which storage space of the dataset is allocated.
+    pub fn alloc_time(&self) -> Result<AllocTime> {
+        h5lock!({
+            let dcpl = PropertyList::from_id(h5try!(H5Dget_create_plist(self.id())))?;
+            let mut alloc_time = H5D_alloc_time_t::H5D_ALLOC_TIME_DEFAULT;
+            h5try!(H5Pget_alloc_time(dcpl.id(), &mut alloc_time));
+            Ok(alloc_time.into())
+        })
+    }
+
     /// Returns `true` if object modification time is tracked by the dataset.
     pub fn tracks_times(&self) -> bool {
         h5lock!({
@@ -127,6 +380,17 @@ impl Dataset {
         }
     }
 
+    /// Returns the number of bytes HDF5 would allocate for the variable-length data (strings
+    /// or sequences) when reading the whole dataset, for estimating memory usage in advance.
+    pub fn vlen_buf_size(&self) -> Result<u64> {
+        h5lock!({
+            let (dtype, space) = (self.dtype()?, self.space()?);
+            let mut size: hsize_t = 0;
+            h5try!(H5Dvlen_get_buf_size(self.id(), dtype.id(), space.id(), &mut size));
+            Ok(size as _)
+        })
+    }
+
     /// Returns default fill value for the dataset if such value is set. Note that conversion
     /// to the requested type is done by HDF5 which may result in loss of precision for
     /// floating-point values if the datatype differs from the datatype of of the dataset.
@@ -154,6 +418,117 @@ impl Dataset {
         h5call!(H5Dget_create_plist(self.id()))
     }
 
+    /// Resizes the dataset like `resize()`, but refuses to shrink any dimension below its
+    /// current extent (which would discard data) unless `allow_truncation` is set.
+    pub fn try_resize<D: Dimension>(&self, d: D, allow_truncation: bool) -> Result<()> {
+        self.checked_resize(d.dims(), allow_truncation, false)
+    }
+
+    /// Resizes the dataset like `try_resize()`, but additionally refuses extents which would
+    /// leave the last chunk along any dimension of a chunked dataset less than half used.
+    pub fn try_resize_aligned<D: Dimension>(&self, d: D, allow_truncation: bool) -> Result<()> {
+        self.checked_resize(d.dims(), allow_truncation, true)
+    }
+
+    fn checked_resize(&self, new: Vec<Ix>, allow_truncation: bool, aligned: bool) -> Result<()> {
+        let old = self.get_shape()?;
+        ensure!(
+            old.ndim() == new.ndim(),
+            "ndim mismatch when resizing: expected {}, got {}",
+            old.ndim(),
+            new.ndim()
+        );
+        if !allow_truncation {
+            for (i, (&o, &n)) in old.iter().zip(new.iter()).enumerate() {
+                ensure!(n >= o, "resizing dimension {} from {} to {} would discard data", i, o, n);
+            }
+        }
+        if let (true, Some(chunks)) = (aligned, self.chunks()) {
+            for (i, (&n, &c)) in new.iter().zip(chunks.iter()).enumerate() {
+                let used = n % c;
+                ensure!(
+                    used == 0 || 2 * used >= c,
+                    "resizing dimension {} to {} would leave its last chunk {}/{} used",
+                    i,
+                    n,
+                    used,
+                    c
+                );
+            }
+        }
+        self.resize(new)
+    }
+
+    /// Creates a memory datatype that consists of a single compound member `name` of type `T`,
+    /// making sure that the dataset is of compound type and has such a member.
+    fn field_dtype<T: H5Type>(&self, name: &str) -> Result<Datatype> {
+        let dtype = self.dtype()?;
+        ensure!(dtype.class()? == DatatypeClass::Compound, "dataset is not of compound type");
+        let c_name = to_cstring(name)?;
+        ensure!(
+            h5lock!({
+                let _e = silence_errors();
+                H5Tget_member_index(dtype.id(), c_name.as_ptr())
+            }) >= 0,
+            "compound field not found: {}",
+            name
+        );
+        let field_dtype = Datatype::from_type::<T>()?;
+        field_dtype.assert_layout_matches::<T>()?;
+        let mem_dtype = Datatype::new_compound(mem::size_of::<T>())?;
+        mem_dtype.insert_member(name, 0, &field_dtype)?;
+        Ok(mem_dtype)
+    }
+
+    /// Reads a single field of all records of a compound dataset in memory order.
+    pub fn read_field<T: H5Type>(&self, name: &str) -> Result<Vec<T>> {
+        let mem_dtype = self.field_dtype::<T>(name)?;
+        let size = self.space()?.size();
+        let mut vec = Vec::with_capacity(size);
+        unsafe {
+            vec.set_len(size);
+        }
+        h5try!(H5Dread(
+            self.id(),
+            mem_dtype.id(),
+            H5S_ALL,
+            H5S_ALL,
+            H5P_DEFAULT,
+            vec.as_mut_ptr() as *mut _
+        ));
+        Ok(vec)
+    }
+
+    /// Overwrites a single field of all records of a compound dataset, leaving the other
+    /// fields intact.
+    ///
+    /// The number of elements in `data` must match the number of elements in the dataset.
+    pub fn write_field<T: H5Type>(&self, name: &str, data: &[T]) -> Result<()> {
+        let mem_dtype = self.field_dtype::<T>(name)?;
+        let size = self.space()?.size();
+        ensure!(
+            data.len() == size,
+            "length mismatch when writing field: memory = {}, destination = {}",
+            data.len(),
+            size
+        );
+        let dxpl = DatasetTransfer::build().preserve(true).finish()?;
+        h5try!(H5Dwrite(
+            self.id(),
+            mem_dtype.id(),
+            H5S_ALL,
+            H5S_ALL,
+            dxpl.id(),
+            data.as_ptr() as *const _
+        ));
+        Ok(())
+    }
+
+    /// Creates a reference to the region of this dataset selected in `selection`.
+    pub fn region_reference(&self, selection: &Dataspace) -> Result<RegionReference> {
+        RegionReference::new(self, selection)
+    }
+
     pub fn resize<D: Dimension>(&self, d: D) -> Result<()> {
         let mut dims: Vec<hsize_t> = vec![];
         for dim in &d.dims() {
@@ -173,6 +548,20 @@ pub struct DatasetBuilder<T> {
     track_times: bool,
     resizable: bool,
     fill_value: Option<T>,
+    alloc_time: Option<AllocTime>,
+    #[cfg(hdf5_1_10_0)]
+    virtual_mappings: Vec<VirtualMapping>,
+    external: Vec<(String, u64, u64)>,
+}
+
+/// Maps a selection of a virtual dataset onto a selection of a source dataset.
+#[cfg(hdf5_1_10_0)]
+#[derive(Clone)]
+struct VirtualMapping {
+    vspace: Dataspace,
+    src_file: String,
+    src_dset: String,
+    src_space: Dataspace,
 }
 
 impl<T: H5Type> DatasetBuilder<T> {
@@ -193,6 +582,10 @@ impl<T: H5Type> DatasetBuilder<T> {
                 track_times: false,
                 resizable: false,
                 fill_value: None,
+                alloc_time: None,
+                #[cfg(hdf5_1_10_0)]
+                virtual_mappings: Vec::new(),
+                external: Vec::new(),
             }
         })
     }
@@ -207,6 +600,12 @@ impl<T: H5Type> DatasetBuilder<T> {
         self
     }
 
+    /// Set the time at which storage space is allocated (defaults to `AllocTime::Default`).
+    pub fn alloc_time(&mut self, alloc_time: AllocTime) -> &mut Self {
+        self.alloc_time = Some(alloc_time);
+        self
+    }
+
     /// Disable chunking.
     pub fn no_chunk(&mut self) -> &mut Self {
         self.chunk = Chunk::None;
@@ -250,6 +649,8 @@ impl<T: H5Type> DatasetBuilder<T> {
     }
 
     /// Enable gzip compression with a specified level (0-9).
+    ///
+    /// Creating the dataset fails if the deflate filter is not available in the HDF5 library.
     pub fn gzip(&mut self, level: u8) -> &mut Self {
         self.filters.gzip(level);
         self
@@ -282,6 +683,35 @@ impl<T: H5Type> DatasetBuilder<T> {
         self
     }
 
+    /// Makes the dataset virtual and maps the selection of `vspace` (a dataspace with the
+    /// shape of the dataset being created) onto the selection of `src_space` in the dataset
+    /// `src_dset` of the file `src_file`; may be called repeatedly to add more mappings.
+    ///
+    /// Use `"."` as `src_file` to refer to the file the virtual dataset is created in.
+    /// Virtual datasets cannot be chunked, filtered or resizable.
+    #[cfg(hdf5_1_10_0)]
+    pub fn virtual_mapping(
+        &mut self, vspace: &Dataspace, src_file: &str, src_dset: &str, src_space: &Dataspace,
+    ) -> &mut Self {
+        self.virtual_mappings.push(VirtualMapping {
+            vspace: vspace.copy(),
+            src_file: src_file.into(),
+            src_dset: src_dset.into(),
+            src_space: src_space.copy(),
+        });
+        self
+    }
+
+    /// Stores the raw data of the dataset in the external file `path`, using `size` bytes
+    /// starting at `offset`; may be called repeatedly to spread the data over several
+    /// files (or several segments of the same file), in the order the calls were made.
+    ///
+    /// Datasets with external storage cannot be chunked, filtered or resizable.
+    pub fn external(&mut self, path: &str, offset: u64, size: u64) -> &mut Self {
+        self.external.push((path.into(), offset, size));
+        self
+    }
+
     fn make_dcpl<D: Dimension>(&self, datatype: &Datatype, shape: D) -> Result<PropertyList> {
         h5lock!({
             let dcpl = self.filters.to_dcpl(datatype)?;
@@ -293,6 +723,46 @@ impl<T: H5Type> DatasetBuilder<T> {
                 h5try!(H5Pset_fill_value(id, datatype.id(), fill_value as *const _ as *const _));
             }
 
+            if let Some(alloc_time) = self.alloc_time {
+                h5try!(H5Pset_alloc_time(id, alloc_time.into()));
+            }
+
+            #[cfg(hdf5_1_10_0)]
+            {
+                if !self.virtual_mappings.is_empty() {
+                    let chunked = matches!(self.chunk, Chunk::Manual(_) | Chunk::Infer);
+                    ensure!(
+                        !chunked && !self.filters.has_filters() && !self.resizable,
+                        "Virtual datasets cannot be chunked, filtered or resizable"
+                    );
+                    for mapping in &self.virtual_mappings {
+                        let src_file = to_cstring(mapping.src_file.as_ref())?;
+                        let src_dset = to_cstring(mapping.src_dset.as_ref())?;
+                        h5try!(H5Pset_virtual(
+                            id,
+                            mapping.vspace.id(),
+                            src_file.as_ptr(),
+                            src_dset.as_ptr(),
+                            mapping.src_space.id()
+                        ));
+                    }
+                    return Ok(dcpl);
+                }
+            }
+
+            if !self.external.is_empty() {
+                let chunked = matches!(self.chunk, Chunk::Manual(_) | Chunk::Infer);
+                ensure!(
+                    !chunked && !self.filters.has_filters() && !self.resizable,
+                    "External storage requires contiguous layout (no chunking, filters or resizing)"
+                );
+                for &(ref path, offset, size) in &self.external {
+                    let path = to_cstring(path.as_ref())?;
+                    h5try!(H5Pset_external(id, path.as_ptr(), offset as _, size as _));
+                }
+                return Ok(dcpl);
+            }
+
             if let Chunk::None = self.chunk {
                 ensure!(
                     !self.filters.has_filters(),
@@ -446,11 +916,13 @@ pub mod tests {
     use std::io::Read;
 
     use hdf5_sys::{h5d::H5Dwrite, h5s::H5S_ALL};
+    use hdf5_types::{IntSize, TypeDescriptor, VarLenArray, VarLenUnicode};
+    use ndarray::s;
 
     use crate::filters::{gzip_available, szip_available};
     use crate::internal_prelude::*;
 
-    use super::infer_chunk_size;
+    use super::{infer_chunk_size, AllocTime};
 
     #[test]
     pub fn test_infer_chunk_size() {
@@ -535,6 +1007,63 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_try_resize() {
+        with_tmp_file(|file| {
+            let ds = file
+                .new_dataset::<u32>()
+                .chunk((4, 4))
+                .resizable(true)
+                .create("foo", (4, 4))
+                .unwrap();
+            ds.write_raw(&(0..16).collect::<Vec<_>>()).unwrap();
+
+            ds.try_resize((6, 4), false).unwrap();
+            assert_eq!(ds.shape(), vec![6, 4]);
+            assert_err!(ds.try_resize((6,), false), "ndim mismatch when resizing");
+            assert_err!(
+                ds.try_resize((2, 4), false),
+                "resizing dimension 0 from 6 to 2 would discard data"
+            );
+            assert_err!(ds.try_resize((6, 5, 1), true), "ndim mismatch when resizing");
+            assert_eq!(ds.shape(), vec![6, 4]);
+
+            ds.try_resize((2, 4), true).unwrap();
+            assert_eq!(ds.shape(), vec![2, 4]);
+            assert_eq!(ds.read_raw::<u32>().unwrap(), (0..8).collect::<Vec<_>>());
+        })
+    }
+
+    #[test]
+    pub fn test_try_resize_aligned() {
+        with_tmp_file(|file| {
+            let ds = file
+                .new_dataset::<u32>()
+                .chunk((4, 4))
+                .resizable(true)
+                .create("foo", (4, 4))
+                .unwrap();
+
+            assert_err!(
+                ds.try_resize_aligned((5, 4), false),
+                "resizing dimension 0 to 5 would leave its last chunk 1/4 used"
+            );
+            assert_err!(
+                ds.try_resize_aligned((8, 9), false),
+                "resizing dimension 1 to 9 would leave its last chunk 1/4 used"
+            );
+            assert_eq!(ds.shape(), vec![4, 4]);
+            ds.try_resize((5, 4), false).unwrap();
+            assert_eq!(ds.shape(), vec![5, 4]);
+
+            ds.try_resize_aligned((6, 8), false).unwrap();
+            assert_eq!(ds.shape(), vec![6, 8]);
+            assert_err!(ds.try_resize_aligned((4, 8), false), "would discard data");
+            ds.try_resize_aligned((4, 8), true).unwrap();
+            assert_eq!(ds.shape(), vec![4, 8]);
+        })
+    }
+
     #[test]
     pub fn test_invalid_chunk() {
         with_tmp_file(|file| {
@@ -733,6 +1262,43 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_storage_size_compressed() {
+        if !gzip_available() {
+            return;
+        }
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().chunk(1000).gzip(9).create("foo", 10000).unwrap();
+            ds.write_raw(&vec![7; 10000]).unwrap();
+            let logical = (ds.size() * ds.dtype().unwrap().size()) as u64;
+            assert_eq!(logical, 40000);
+            assert!(ds.storage_size() > 0);
+            assert!(ds.storage_size() * 10 < logical);
+        })
+    }
+
+    #[test]
+    pub fn test_copy_to() {
+        if !gzip_available() {
+            return;
+        }
+        with_tmp_dir(|dir| {
+            let src = File::create(dir.join("src.h5")).unwrap();
+            let dst = File::create(dir.join("dst.h5")).unwrap();
+            let ds = src.new_dataset::<u32>().chunk(10).gzip(6).create("foo", 100).unwrap();
+            ds.write_raw(&(0..100).collect::<Vec<_>>()).unwrap();
+            ds.new_attr::<u8>().create("bar", ()).unwrap().write_scalar(&7).unwrap();
+
+            let copy = ds.copy_to(&dst, "a/b").unwrap();
+            assert_eq!(copy.filters().get_gzip(), Some(6));
+            assert_eq!(copy.chunks(), Some(vec![10]));
+            assert_eq!(copy.read_raw::<u32>().unwrap(), (0..100).collect::<Vec<_>>());
+            assert_eq!(copy.attr("bar").unwrap().read_scalar::<u8>().unwrap(), 7);
+            assert_eq!(dst.dataset("a/b").unwrap().name(), "/a/b");
+            assert_err!(ds.copy_to(&dst, "a/b"), "unable to copy object");
+        })
+    }
+
     #[test]
     pub fn test_datatype() {
         with_tmp_file(|file| {
@@ -759,6 +1325,469 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_read_write_scalar() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<f64>().create("x", ()).unwrap();
+            assert!(ds.is_scalar());
+            ds.write_scalar(&3.25).unwrap();
+            assert_eq!(ds.read_scalar::<f64>().unwrap(), 3.25);
+
+            let ds = file.new_dataset::<f64>().create("y", 1).unwrap();
+            assert_err!(ds.write_scalar(&3.25), "ndim mismatch: expected scalar, got 1");
+            assert_err!(ds.read_scalar::<f64>(), "ndim mismatch: expected scalar, got 1");
+        })
+    }
+
+    #[test]
+    pub fn test_read_write_transfer_plist() {
+        use hdf5_sys::{h5d::H5Dcreate2, h5t::H5Tcopy};
+
+        use crate::globals::H5T_STD_I32BE;
+        use crate::plist::DatasetTransfer;
+
+        with_tmp_file(|file| {
+            let dtype = Datatype::from_id(h5call!(H5Tcopy(*H5T_STD_I32BE)).unwrap()).unwrap();
+            let space = Dataspace::try_new(100, false).unwrap();
+            let name = to_cstring("foo").unwrap();
+            let ds = Dataset::from_id(
+                h5call!(H5Dcreate2(
+                    file.id(),
+                    name.as_ptr(),
+                    dtype.id(),
+                    space.id(),
+                    H5P_DEFAULT,
+                    H5P_DEFAULT,
+                    H5P_DEFAULT
+                ))
+                .unwrap(),
+            )
+            .unwrap();
+            let dxpl = DatasetTransfer::build().buffer_size(64 * 1024).finish().unwrap();
+            assert_eq!(dxpl.buffer_size(), 64 * 1024);
+
+            let data: Vec<i32> = (-50..50).map(|x| x * 0x0101_0101).collect();
+            ds.as_writer().dxpl(&dxpl).write_raw(&data).unwrap();
+            assert_eq!(ds.as_reader().dxpl(&dxpl).read_raw::<i32>().unwrap(), data);
+            assert_eq!(ds.read_raw::<i32>().unwrap(), data);
+        })
+    }
+
+    #[test]
+    pub fn test_row_chunks() {
+        with_tmp_file(|file| {
+            let data: Vec<u32> = (0..30).collect();
+            let ds = file.new_dataset::<u32>().create("foo", (10, 3)).unwrap();
+            ds.write_raw(&data).unwrap();
+
+            let chunks: Vec<_> = ds.row_chunks::<u32>(4).unwrap().map(|c| c.unwrap()).collect();
+            let sizes: Vec<_> = chunks.iter().map(|c| c.len() / 3).collect();
+            assert_eq!(sizes, vec![4, 4, 2]);
+            assert_eq!(chunks.concat(), ds.read_raw::<u32>().unwrap());
+            assert_eq!(ds.row_chunks::<u32>(10).unwrap().count(), 1);
+            assert_eq!(ds.row_chunks::<u32>(100).unwrap().count(), 1);
+
+            assert_err!(ds.row_chunks::<u32>(0), "number of rows per chunk must be positive");
+            let ds = file.new_dataset::<u32>().create("bar", ()).unwrap();
+            assert_err!(ds.row_chunks::<u32>(1), "cannot be read from scalar datasets");
+            let ds = file.new_dataset::<u32>().create("baz", (0, 3)).unwrap();
+            assert_eq!(ds.row_chunks::<u32>(1).unwrap().count(), 0);
+        })
+    }
+
+    #[test]
+    pub fn test_read_into() {
+        with_tmp_file(|file| {
+            let data: Vec<u16> = (0..100).collect();
+            let ds = file.new_dataset::<u16>().create("foo", 100).unwrap();
+            ds.write_raw(&data).unwrap();
+
+            let mut buf = vec![0u16; 100];
+            ds.read_into(&mut buf).unwrap();
+            assert_eq!(buf, data);
+            buf.iter_mut().for_each(|x| *x = 0);
+            ds.read_into(&mut buf).unwrap();
+            assert_eq!(buf, data);
+            assert_err!(
+                ds.read_into(&mut buf[..99]),
+                "length mismatch when reading: buffer = 99, source = 100"
+            );
+
+            let mut buf = [0u16; 10];
+            ds.read_slice_into(s![20..40;2], &mut buf).unwrap();
+            assert_eq!(buf.to_vec(), (20..40).step_by(2).collect::<Vec<_>>());
+            assert_err!(ds.read_slice_into(s![0..5], &mut buf), "length mismatch when reading");
+            assert_err!(ds.read_slice_into(s![0..5, 0..2], &mut buf), "slice dimension mismatch");
+        })
+    }
+
+    #[test]
+    pub fn test_write_from_iter() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<f64>().create("foo", (3, 4)).unwrap();
+            ds.write_from_iter((0..12).map(|x| x as f64)).unwrap();
+            let expected: Vec<f64> = (0..12).map(|x| x as f64).collect();
+            assert_eq!(ds.read_raw::<f64>().unwrap(), expected);
+            assert_eq!(ds.read_2d::<f64>().unwrap()[[2, 1]], 9.0);
+            assert_err!(
+                ds.write_from_iter((0..11).map(|x| x as f64)),
+                "length mismatch when writing: memory = 11, destination = 12"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_read_sign_mismatch() {
+        with_tmp_file(|file| {
+            let big = i64::max_value() as u64 + 1;
+            let ds = file.new_dataset::<u64>().create("foo", 2).unwrap();
+            ds.write_raw(&[1, big]).unwrap();
+            assert_err!(
+                ds.read_raw::<i64>(),
+                "integer sign mismatch: unsigned integer stored, signed integer requested"
+            );
+            assert_err!(ds.read_raw::<i8>(), "integer sign mismatch");
+            assert_eq!(ds.read_raw::<u64>().unwrap(), vec![1, big]);
+            let clamped = ds.as_reader().reinterpret(true).read_raw::<i64>().unwrap();
+            assert_eq!(clamped, vec![1, i64::max_value()]);
+
+            let ds = file.new_dataset::<u32>().create("bar", 1).unwrap();
+            ds.write_raw(&[u32::max_value()]).unwrap();
+            assert_eq!(ds.read_raw::<i64>().unwrap(), vec![i64::from(u32::max_value())]);
+            assert_err!(ds.read_raw::<i32>(), "integer sign mismatch");
+
+            let ds = file.new_dataset::<i16>().create("baz", 1).unwrap();
+            ds.write_raw(&[-1]).unwrap();
+            assert_err!(
+                ds.read_raw::<u64>(),
+                "integer sign mismatch: signed integer stored, unsigned integer requested"
+            );
+            assert_eq!(ds.read_raw::<f32>().unwrap(), vec![-1.]);
+        })
+    }
+
+    #[test]
+    pub fn test_fill() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<i32>().create("foo", 1000).unwrap();
+            ds.fill(&7).unwrap();
+            let data = ds.read_raw::<i32>().unwrap();
+            for &i in &[0, 1, 499, 998, 999] {
+                assert_eq!(data[i], 7);
+            }
+
+            let ds = file.new_dataset::<i32>().create("bar", (700, 500)).unwrap();
+            ds.fill(&-1).unwrap();
+            assert!(ds.read_raw::<i32>().unwrap().iter().all(|&x| x == -1));
+            ds.fill(&2).unwrap();
+            assert!(ds.read_raw::<i32>().unwrap().iter().all(|&x| x == 2));
+
+            let ds = file.new_dataset::<VarLenUnicode>().create("baz", (3, 2)).unwrap();
+            let s: VarLenUnicode = "hello".parse().unwrap();
+            ds.fill(&s).unwrap();
+            assert_eq!(ds.read_raw::<VarLenUnicode>().unwrap(), vec![s.clone(); 6]);
+
+            file.new_dataset::<u8>().create("empty", (0, 2)).unwrap().fill(&1).unwrap();
+            let scalar = file.new_dataset::<f64>().create("scalar", ()).unwrap();
+            scalar.fill(&0.5).unwrap();
+            assert_eq!(scalar.read_scalar::<f64>().unwrap(), 0.5);
+            let attr = ds.new_attr::<u16>().create("attr", 4).unwrap();
+            attr.fill(&3).unwrap();
+            assert_eq!(attr.read_raw::<u16>().unwrap(), vec![3; 4]);
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_3)]
+    pub fn test_direct_chunk_io() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().chunk((2, 3)).create("foo", (4, 6)).unwrap();
+            let arr = ndarray::Array2::from_shape_fn((4, 6), |(i, j)| (i * 6 + j) as u32);
+            ds.write(&arr).unwrap();
+
+            let (filter_mask, bytes) = ds.read_chunk(&[2, 3]).unwrap();
+            assert_eq!(filter_mask, 0);
+            assert_eq!(bytes.len(), 2 * 3 * std::mem::size_of::<u32>());
+            assert_eq!(bytes.len(), ds.storage_size() as usize / 4);
+
+            ds.write_chunk(&[0, 0], filter_mask, &bytes).unwrap();
+            assert_eq!(
+                ds.read_slice_2d::<u32, _>(&s![0..2, 0..3]).unwrap(),
+                arr.slice(s![2..4, 3..6])
+            );
+
+            assert_err!(ds.read_chunk(&[1, 3]), "chunk offset is not aligned");
+            assert_err!(ds.write_chunk(&[0], 0, &bytes), "chunk offset ndim mismatch");
+            let ds = file.new_dataset::<u32>().no_chunk().create("bar", 6).unwrap();
+            assert_err!(ds.read_chunk(&[0]), "direct chunk I/O requires a chunked dataset");
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_5)]
+    pub fn test_chunk_info() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u16>().chunk((2, 5)).create("foo", (6, 10)).unwrap();
+            assert_eq!(ds.num_chunks().unwrap(), 0);
+            ds.write(&ndarray::Array2::<u16>::ones((6, 10))).unwrap();
+            assert_eq!(ds.num_chunks().unwrap(), 6);
+
+            let mut offsets = vec![];
+            for i in 0..6 {
+                let info = ds.chunk_info(i).unwrap();
+                assert_eq!(info.size, 2 * 5 * 2);
+                assert_eq!(info.filter_mask, 0);
+                assert!(info.addr > 0);
+                offsets.push(info.offset);
+            }
+            offsets.sort();
+            assert_eq!(offsets[0], vec![0, 0]);
+            assert_eq!(offsets[5], vec![4, 5]);
+        })
+    }
+
+    #[test]
+    pub fn test_char() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().create("foo", 2).unwrap();
+            ds.write_from_iter(['A', '€'].iter().map(|&c| u32::from(c))).unwrap();
+            assert_eq!(ds.read_chars().unwrap(), vec!['A', '€']);
+            assert_eq!(ds.read_raw::<u32>().unwrap(), vec![0x41, 0x20ac]);
+
+            let ds = file.new_dataset::<u32>().create("bar", 3).unwrap();
+            ds.write_raw(&[0x61, 0xd800, 0x110000]).unwrap();
+            assert_err!(ds.read_chars(), "invalid Unicode scalar value: 0xd800");
+            ds.write_raw(&[0x61, 0x10ffff, 0]).unwrap();
+            assert_eq!(ds.read_chars().unwrap(), vec!['a', '\u{10ffff}', '\0']);
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_0)]
+    pub fn test_virtual_dataset() {
+        with_tmp_dir(|dir| {
+            let src_path = dir.join("src.h5");
+            let src = File::create(&src_path).unwrap();
+            src.new_dataset::<i32>()
+                .create("a", (2, 3))
+                .unwrap()
+                .write_raw(&[1, 2, 3, 4, 5, 6])
+                .unwrap();
+            drop(src);
+
+            let file = File::create(dir.join("vds.h5")).unwrap();
+            file.new_dataset::<i32>()
+                .create("b", (2, 2))
+                .unwrap()
+                .write_raw(&[7, 8, 9, 10])
+                .unwrap();
+
+            let vspace = Dataspace::try_new((2, 5), false).unwrap();
+            let (a_space, b_space) = (
+                Dataspace::try_new((2, 3), false).unwrap(),
+                Dataspace::try_new((2, 2), false).unwrap(),
+            );
+            let mut builder = file.new_dataset::<i32>();
+            vspace.select_slice(s![.., 0..3]).unwrap();
+            builder.virtual_mapping(&vspace, src_path.to_str().unwrap(), "a", &a_space);
+            vspace.select_slice(s![.., 3..5]).unwrap();
+            builder.virtual_mapping(&vspace, ".", "b", &b_space);
+            let ds = builder.create("vds", (2, 5)).unwrap();
+            assert!(!ds.is_chunked());
+            assert_eq!(
+                ds.read_2d::<i32>().unwrap(),
+                ndarray::arr2(&[[1, 2, 3, 7, 8], [4, 5, 6, 9, 10]])
+            );
+
+            assert_err!(
+                builder.chunk((1, 5)).create("vds2", (2, 5)),
+                "Virtual datasets cannot be chunked, filtered or resizable"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_external() {
+        with_tmp_dir(|dir| {
+            let file = File::create(dir.join("foo.h5")).unwrap();
+            let ext_path = dir.join("ext.bin");
+            let ext = ext_path.to_str().unwrap();
+            let data: Vec<i32> = (0..12).map(|x| x * 1000 - 5).collect();
+
+            let ds = file
+                .new_dataset::<i32>()
+                .external(ext, 0, 24)
+                .external(ext, 24, 24)
+                .create("ext", (3, 4))
+                .unwrap();
+            assert!(!ds.is_chunked());
+            assert_eq!(
+                ds.external_files().unwrap(),
+                vec![(ext.to_owned(), 0, 24), (ext.to_owned(), 24, 24)]
+            );
+            ds.write_raw(&data).unwrap();
+            file.flush().unwrap();
+
+            let bytes = std::fs::read(&ext_path).unwrap();
+            let expected: Vec<u8> = data.iter().flat_map(|x| x.to_ne_bytes().to_vec()).collect();
+            assert_eq!(bytes, expected);
+            assert_eq!(ds.read_raw::<i32>().unwrap(), data);
+
+            assert!(file
+                .new_dataset::<i32>()
+                .create("plain", 3)
+                .unwrap()
+                .external_files()
+                .unwrap()
+                .is_empty());
+            assert_err!(
+                file.new_dataset::<i32>().external(ext, 48, 12).chunk(3).create("chunked", 3),
+                "External storage requires contiguous layout"
+            );
+            assert_err!(
+                file.new_dataset::<i32>().external(ext, 48, 12).resizable(true).create("res", 3),
+                "External storage requires contiguous layout"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_alloc_time() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().create("default", (10, 10)).unwrap();
+            assert_eq!(ds.alloc_time().unwrap(), AllocTime::Late);
+            assert_eq!(ds.storage_size(), 0);
+
+            let ds = file
+                .new_dataset::<u32>()
+                .alloc_time(AllocTime::Early)
+                .create("early", (10, 10))
+                .unwrap();
+            assert_eq!(ds.alloc_time().unwrap(), AllocTime::Early);
+            assert_eq!(ds.storage_size(), 400);
+
+            let ds = file
+                .new_dataset::<u32>()
+                .chunk((5, 5))
+                .alloc_time(AllocTime::Early)
+                .create("early_chunked", (10, 10))
+                .unwrap();
+            assert_eq!(ds.storage_size(), 400);
+            assert_eq!(
+                file.new_dataset::<u8>()
+                    .chunk(2)
+                    .create("chunked", 4)
+                    .unwrap()
+                    .alloc_time()
+                    .unwrap(),
+                AllocTime::Incremental
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_fixed_array_elements() {
+        with_tmp_file(|file| {
+            let data = vec![[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+            let ds = file.new_dataset::<[f32; 3]>().create("foo", 2).unwrap();
+            ds.write_raw(&data).unwrap();
+
+            let ds = file.dataset("foo").unwrap();
+            assert!(ds.dtype().unwrap().is::<[f32; 3]>());
+            assert_eq!(ds.read_raw::<[f32; 3]>().unwrap(), data);
+            assert_eq!(ds.read_1d::<[f32; 3]>().unwrap().to_vec(), data);
+            assert_eq!(ds.read_raw::<[f64; 3]>().unwrap(), vec![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+            assert_err!(ds.read_raw::<[f32; 2]>(), "no conversion paths found");
+            assert_err!(ds.read_raw::<f32>(), "no conversion paths found");
+        })
+    }
+
+    #[test]
+    pub fn test_dataset_attrs() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<f32>().create("foo", 3).unwrap();
+            assert!(ds.attr_names().unwrap().is_empty());
+            let units: VarLenUnicode = "m/s".parse().unwrap();
+            ds.new_attr::<VarLenUnicode>()
+                .create("units", ())
+                .unwrap()
+                .write_scalar(&units)
+                .unwrap();
+            ds.new_attr::<u32>().create("count", ()).unwrap().write_scalar(&3).unwrap();
+
+            let ds = file.dataset("foo").unwrap();
+            assert_eq!(ds.attr("units").unwrap().read_scalar::<VarLenUnicode>().unwrap(), units);
+            assert_eq!(ds.attr_names().unwrap(), vec!["count", "units"]);
+            assert!(ds.has_attr("units"));
+            assert!(!file.has_attr("units"));
+        })
+    }
+
+    #[test]
+    pub fn test_layout_mismatch() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Bad(u32);
+
+        unsafe impl H5Type for Bad {
+            fn type_descriptor() -> TypeDescriptor {
+                TypeDescriptor::Integer(IntSize::U8)
+            }
+        }
+
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<i64>().create("foo", 3).unwrap();
+            ds.write_raw(&[1, 2, 3]).unwrap();
+            assert_err!(
+                ds.read_raw::<Bad>(),
+                "datatype size mismatch: 4 bytes in memory, 8 bytes in datatype"
+            );
+            assert_err!(ds.write_raw(&[Bad(1), Bad(2), Bad(3)]), "datatype size mismatch");
+            assert_eq!(ds.read_raw::<i32>().unwrap(), vec![1, 2, 3]);
+            assert_err!(ds.as_reader().no_convert().read_raw::<i32>(), "conversion path required");
+            assert!(Datatype::from_type::<i64>().unwrap().assert_layout_matches::<i64>().is_ok());
+        })
+    }
+
+    #[test]
+    pub fn test_read_vlen() {
+        with_tmp_file(|file| {
+            let strings = ["foo", "", "bär", "bazz"];
+            let data: Vec<VarLenUnicode> = strings.iter().map(|s| s.parse().unwrap()).collect();
+            let ds = file.new_dataset::<VarLenUnicode>().create("foo", 4).unwrap();
+            ds.write_raw(&data).unwrap();
+
+            let buf = ds.read_vlen::<VarLenUnicode>().unwrap();
+            assert_eq!(buf.len(), 4);
+            assert_eq!(buf.as_slice()[2].as_str(), "bär");
+            let owned = buf.to_vec();
+            drop(buf);
+            assert_eq!(owned, data);
+
+            let ds = file.new_dataset::<VarLenUnicode>().create("bar", 0).unwrap();
+            assert!(ds.read_vlen::<VarLenUnicode>().unwrap().is_empty());
+        })
+    }
+
+    #[test]
+    pub fn test_vlen_buf_size() {
+        with_tmp_file(|file| {
+            let strings = ["foo", "", "bär", "bazz"];
+            let data: Vec<VarLenUnicode> = strings.iter().map(|s| s.parse().unwrap()).collect();
+            let ds = file.new_dataset::<VarLenUnicode>().create("foo", 4).unwrap();
+            ds.write_raw(&data).unwrap();
+            let total: usize = strings.iter().map(|s| s.len()).sum();
+            assert!(ds.vlen_buf_size().unwrap() >= total as u64);
+
+            let data = vec![VarLenArray::from_slice(&[1u32, 2, 3]), VarLenArray::from_slice(&[4])];
+            let ds = file.new_dataset::<VarLenArray<u32>>().create("bar", 2).unwrap();
+            ds.write_raw(&data).unwrap();
+            assert_eq!(ds.vlen_buf_size().unwrap(), 16);
+
+            let ds = file.new_dataset::<VarLenUnicode>().create("baz", 0).unwrap();
+            assert_eq!(ds.vlen_buf_size().unwrap(), 0);
+        })
+    }
+
     #[test]
     pub fn test_fill_value() {
         with_tmp_file(|file| {
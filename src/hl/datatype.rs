@@ -1,21 +1,28 @@
 use std::borrow::Borrow;
 use std::cmp::{Ordering, PartialEq, PartialOrd};
+use std::convert::TryFrom;
 use std::fmt::{self, Debug, Display};
+use std::mem;
 use std::ops::Deref;
+use std::ptr;
 
 use hdf5_sys::h5t::{
-    H5T_cdata_t, H5T_class_t, H5T_cset_t, H5T_str_t, H5Tarray_create2, H5Tcompiler_conv, H5Tcopy,
-    H5Tcreate, H5Tenum_create, H5Tenum_insert, H5Tequal, H5Tfind, H5Tget_array_dims2,
-    H5Tget_array_ndims, H5Tget_class, H5Tget_cset, H5Tget_member_name, H5Tget_member_offset,
-    H5Tget_member_type, H5Tget_member_value, H5Tget_nmembers, H5Tget_sign, H5Tget_size,
-    H5Tget_super, H5Tinsert, H5Tis_variable_str, H5Tset_cset, H5Tset_size, H5Tset_strpad,
-    H5Tvlen_create, H5T_VARIABLE,
+    H5T_cdata_t, H5T_class_t, H5T_cset_t, H5T_direction_t, H5T_norm_t, H5T_order_t, H5T_pad_t,
+    H5T_sign_t, H5T_str_t, H5Tarray_create2, H5Tcommitted, H5Tcompiler_conv, H5Tcopy, H5Tcreate,
+    H5Tdecode, H5Tencode, H5Tenum_create, H5Tenum_insert, H5Tenum_nameof, H5Tenum_valueof,
+    H5Tequal, H5Tfind, H5Tget_array_dims2, H5Tget_array_ndims, H5Tget_class, H5Tget_cset,
+    H5Tget_ebias, H5Tget_fields, H5Tget_inpad, H5Tget_member_index, H5Tget_member_name,
+    H5Tget_member_offset, H5Tget_member_type, H5Tget_member_value, H5Tget_native_type,
+    H5Tget_nmembers, H5Tget_norm, H5Tget_order, H5Tget_precision, H5Tget_sign, H5Tget_size,
+    H5Tget_strpad, H5Tget_super, H5Tinsert, H5Tis_variable_str, H5Tpack, H5Tset_cset, H5Tset_ebias,
+    H5Tset_fields, H5Tset_inpad, H5Tset_norm, H5Tset_order, H5Tset_precision, H5Tset_size,
+    H5Tset_strpad, H5Tvlen_create, H5T_VARIABLE,
 };
 use hdf5_types::{
     CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor,
 };
 
-use crate::globals::{H5T_C_S1, H5T_NATIVE_INT, H5T_NATIVE_INT8};
+use crate::globals::{H5T_C_S1, H5T_NATIVE_INT, H5T_NATIVE_INT8, H5T_STD_REF_DSETREG};
 use crate::internal_prelude::*;
 
 #[cfg(target_endian = "big")]
@@ -61,7 +68,32 @@ impl ObjectClass for Datatype {
         &self.0
     }
 
-    // TODO: short_repr()
+    fn short_repr(&self) -> Option<String> {
+        // TODO: describe non-string datatypes as well
+        let _e = silence_errors();
+        if self.ensure_float().is_ok() {
+            if self.is_standard_float() {
+                return None;
+            }
+            let FloatFields { spos, epos, esize, mpos, msize } = self.float_fields().ok()?;
+            return Some(format!(
+                "non-standard {}-byte float (sign {}, exponent {}+{}, mantissa {}+{}, bias {})",
+                self.size(),
+                spos,
+                epos,
+                esize,
+                mpos,
+                msize,
+                self.ebias().ok()?
+            ));
+        }
+        let cset = self.cset().ok()?;
+        if self.is_variable_str() {
+            Some(format!("variable-length {} string", cset))
+        } else {
+            Some(format!("{}-byte {} string, {}", self.size(), cset, self.strpad().ok()?))
+        }
+    }
 }
 
 impl Debug for Datatype {
@@ -119,12 +151,690 @@ impl Default for Conversion {
     }
 }
 
+/// Datatype class.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DatatypeClass {
+    Integer,
+    Float,
+    Time,
+    String,
+    Bitfield,
+    Opaque,
+    Compound,
+    Reference,
+    Enum,
+    VarLen,
+    Array,
+}
+
+/// Character set of a string datatype.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CharSet {
+    #[default]
+    Ascii,
+    Utf8,
+}
+
+impl From<CharSet> for H5T_cset_t {
+    fn from(cset: CharSet) -> Self {
+        match cset {
+            CharSet::Ascii => H5T_cset_t::H5T_CSET_ASCII,
+            CharSet::Utf8 => H5T_cset_t::H5T_CSET_UTF8,
+        }
+    }
+}
+
+impl Display for CharSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            CharSet::Ascii => "ASCII",
+            CharSet::Utf8 => "UTF-8",
+        })
+    }
+}
+
+/// Padding of fixed-length strings shorter than the string datatype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StringPadding {
+    /// Null-terminated, garbage after the terminator.
+    NullTerminate,
+    /// Padded with zeros, not necessarily null-terminated.
+    NullPad,
+    /// Padded with spaces.
+    SpacePad,
+}
+
+impl From<StringPadding> for H5T_str_t {
+    fn from(pad: StringPadding) -> Self {
+        match pad {
+            StringPadding::NullTerminate => H5T_str_t::H5T_STR_NULLTERM,
+            StringPadding::NullPad => H5T_str_t::H5T_STR_NULLPAD,
+            StringPadding::SpacePad => H5T_str_t::H5T_STR_SPACEPAD,
+        }
+    }
+}
+
+impl Display for StringPadding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            StringPadding::NullTerminate => "null-terminated",
+            StringPadding::NullPad => "null-padded",
+            StringPadding::SpacePad => "space-padded",
+        })
+    }
+}
+
+/// Byte order of an atomic datatype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+    Vax,
+    Mixed,
+    None,
+}
+
+impl ByteOrder {
+    fn from_raw(order: H5T_order_t) -> Result<Self> {
+        match order {
+            H5T_order_t::H5T_ORDER_LE => Ok(ByteOrder::LittleEndian),
+            H5T_order_t::H5T_ORDER_BE => Ok(ByteOrder::BigEndian),
+            H5T_order_t::H5T_ORDER_VAX => Ok(ByteOrder::Vax),
+            H5T_order_t::H5T_ORDER_MIXED => Ok(ByteOrder::Mixed),
+            H5T_order_t::H5T_ORDER_NONE => Ok(ByteOrder::None),
+            _ => fail!("Invalid byte order of datatype"),
+        }
+    }
+}
+
+/// Bit positions and sizes of the fields of a floating-point datatype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FloatFields {
+    /// Position of the sign bit.
+    pub spos: usize,
+    /// Position of the lowest exponent bit.
+    pub epos: usize,
+    /// Number of exponent bits.
+    pub esize: usize,
+    /// Position of the lowest mantissa bit.
+    pub mpos: usize,
+    /// Number of mantissa bits.
+    pub msize: usize,
+}
+
+/// Normalization of the mantissa of a floating-point datatype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatNormalization {
+    /// The most significant bit of the mantissa is not stored and is always 1.
+    Implied,
+    /// The most significant bit of the mantissa is stored and is always 1.
+    MsbSet,
+    /// The mantissa is not normalized.
+    None,
+}
+
+impl FloatNormalization {
+    fn from_raw(norm: H5T_norm_t) -> Result<Self> {
+        match norm {
+            H5T_norm_t::H5T_NORM_IMPLIED => Ok(FloatNormalization::Implied),
+            H5T_norm_t::H5T_NORM_MSBSET => Ok(FloatNormalization::MsbSet),
+            H5T_norm_t::H5T_NORM_NONE => Ok(FloatNormalization::None),
+            _ => fail!("Invalid normalization of float datatype"),
+        }
+    }
+}
+
+impl From<FloatNormalization> for H5T_norm_t {
+    fn from(norm: FloatNormalization) -> Self {
+        match norm {
+            FloatNormalization::Implied => H5T_norm_t::H5T_NORM_IMPLIED,
+            FloatNormalization::MsbSet => H5T_norm_t::H5T_NORM_MSBSET,
+            FloatNormalization::None => H5T_norm_t::H5T_NORM_NONE,
+        }
+    }
+}
+
+/// Value of unused bits in an atomic datatype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitPadding {
+    /// Set to zero.
+    Zero,
+    /// Set to one.
+    One,
+    /// Left as is (background).
+    Background,
+}
+
+impl BitPadding {
+    fn from_raw(pad: H5T_pad_t) -> Result<Self> {
+        match pad {
+            H5T_pad_t::H5T_PAD_ZERO => Ok(BitPadding::Zero),
+            H5T_pad_t::H5T_PAD_ONE => Ok(BitPadding::One),
+            H5T_pad_t::H5T_PAD_BACKGROUND => Ok(BitPadding::Background),
+            _ => fail!("Invalid bit padding of datatype"),
+        }
+    }
+}
+
+impl From<BitPadding> for H5T_pad_t {
+    fn from(pad: BitPadding) -> Self {
+        match pad {
+            BitPadding::Zero => H5T_pad_t::H5T_PAD_ZERO,
+            BitPadding::One => H5T_pad_t::H5T_PAD_ONE,
+            BitPadding::Background => H5T_pad_t::H5T_PAD_BACKGROUND,
+        }
+    }
+}
+
+/// Member of a compound datatype, as reported by `Datatype::describe_full()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompoundMemberDescription {
+    pub name: String,
+    pub offset: usize,
+    pub ty: DatatypeDescription,
+}
+
+/// Structured description of an arbitrary HDF5 datatype.
+///
+/// Unlike `TypeDescriptor`, this covers every datatype class that can be found in a file
+/// (including multi-dimensional arrays, opaque, bitfield and reference types) and also
+/// records byte order; it is meant for inspecting data rather than mapping it to Rust types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DatatypeDescription {
+    Integer {
+        size: usize,
+        signed: bool,
+        order: ByteOrder,
+    },
+    Float {
+        size: usize,
+        order: ByteOrder,
+    },
+    Time {
+        size: usize,
+        order: ByteOrder,
+    },
+    Bitfield {
+        size: usize,
+        order: ByteOrder,
+    },
+    Opaque {
+        size: usize,
+    },
+    Reference {
+        size: usize,
+    },
+    /// String datatype; `size` is `None` for variable-length strings.
+    String {
+        size: Option<usize>,
+        cset: CharSet,
+    },
+    Enum {
+        base: Box<DatatypeDescription>,
+        members: Vec<(String, i64)>,
+    },
+    Compound {
+        size: usize,
+        members: Vec<CompoundMemberDescription>,
+    },
+    Array {
+        base: Box<DatatypeDescription>,
+        dims: Vec<usize>,
+    },
+    VarLen {
+        base: Box<DatatypeDescription>,
+    },
+}
+
 impl Datatype {
+    /// Returns the class of the datatype (without inspecting it any further).
+    pub fn class(&self) -> Result<DatatypeClass> {
+        use hdf5_sys::h5t::H5T_class_t::*;
+        Ok(match h5lock!(H5Tget_class(self.id())) {
+            H5T_INTEGER => DatatypeClass::Integer,
+            H5T_FLOAT => DatatypeClass::Float,
+            H5T_TIME => DatatypeClass::Time,
+            H5T_STRING => DatatypeClass::String,
+            H5T_BITFIELD => DatatypeClass::Bitfield,
+            H5T_OPAQUE => DatatypeClass::Opaque,
+            H5T_COMPOUND => DatatypeClass::Compound,
+            H5T_REFERENCE => DatatypeClass::Reference,
+            H5T_ENUM => DatatypeClass::Enum,
+            H5T_VLEN => DatatypeClass::VarLen,
+            H5T_ARRAY => DatatypeClass::Array,
+            _ => fail!("Invalid datatype class"),
+        })
+    }
+
     /// Get the total size of the datatype in bytes.
     pub fn size(&self) -> usize {
         h5call!(H5Tget_size(self.id())).unwrap_or(0) as usize
     }
 
+    /// Sets the total size of the datatype in bytes (e.g. the length of a fixed-size
+    /// string or of an opaque type).
+    pub fn set_size(&self, size: usize) -> Result<()> {
+        ensure!(size != H5T_VARIABLE, "Use set_variable() to make a variable-length string");
+        self.set_size_raw(size)
+    }
+
+    /// Converts a string datatype into a variable-length string.
+    pub fn set_variable(&self) -> Result<()> {
+        self.ensure_string()?;
+        self.set_size_raw(H5T_VARIABLE)
+    }
+
+    fn set_size_raw(&self, size: usize) -> Result<()> {
+        h5lock!({
+            ensure!(h5try!(H5Tcommitted(self.id())) <= 0, "Cannot modify a committed datatype");
+            h5call!(H5Tset_size(self.id(), size as _)).and(Ok(()))
+        })
+    }
+
+    /// Returns `true` if this is a variable-length string datatype.
+    pub fn is_variable_str(&self) -> bool {
+        h5call!(H5Tis_variable_str(self.id())).map(|x| x > 0).unwrap_or(false)
+    }
+
+    fn ensure_string(&self) -> Result<()> {
+        ensure!(
+            h5lock!(H5Tget_class(self.id())) == H5T_class_t::H5T_STRING,
+            "Expected a string datatype"
+        );
+        Ok(())
+    }
+
+    fn ensure_float(&self) -> Result<()> {
+        ensure!(
+            h5lock!(H5Tget_class(self.id())) == H5T_class_t::H5T_FLOAT,
+            "Expected a float datatype"
+        );
+        Ok(())
+    }
+
+    fn ensure_integer(&self) -> Result<()> {
+        ensure!(
+            h5lock!(H5Tget_class(self.id())) == H5T_class_t::H5T_INTEGER,
+            "Expected an integer datatype"
+        );
+        Ok(())
+    }
+
+    /// Returns `true` if an integer datatype is signed.
+    pub fn is_signed(&self) -> Result<bool> {
+        self.ensure_integer()?;
+        match h5lock!(H5Tget_sign(self.id())) {
+            H5T_sign_t::H5T_SGN_NONE => Ok(false),
+            H5T_sign_t::H5T_SGN_2 => Ok(true),
+            _ => fail!("Invalid sign of integer datatype"),
+        }
+    }
+
+    /// Fails if both types are integers of different signedness, unless the destination
+    /// type is wide enough to hold every value of the source type.
+    pub(crate) fn ensure_sign_compatible(&self, dst: &Self) -> Result<()> {
+        if self.ensure_integer().is_err() || dst.ensure_integer().is_err() {
+            return Ok(());
+        }
+        let (src_signed, dst_signed) = (self.is_signed()?, dst.is_signed()?);
+        if src_signed != dst_signed && (src_signed || dst.size() <= self.size()) {
+            let sign = |signed| if signed { "signed" } else { "unsigned" };
+            fail!(
+                "integer sign mismatch: {} integer stored, {} integer requested",
+                sign(src_signed),
+                sign(dst_signed)
+            );
+        }
+        Ok(())
+    }
+
+    /// Get the character set of a string datatype.
+    pub fn cset(&self) -> Result<CharSet> {
+        self.ensure_string()?;
+        match h5lock!(H5Tget_cset(self.id())) {
+            H5T_cset_t::H5T_CSET_ASCII => Ok(CharSet::Ascii),
+            H5T_cset_t::H5T_CSET_UTF8 => Ok(CharSet::Utf8),
+            _ => fail!("Invalid encoding for string datatype"),
+        }
+    }
+
+    /// Set the character set of a string datatype.
+    pub fn set_cset(&self, cset: CharSet) -> Result<()> {
+        self.ensure_string()?;
+        h5call!(H5Tset_cset(self.id(), cset.into())).and(Ok(()))
+    }
+
+    /// Get the padding of a string datatype.
+    pub fn strpad(&self) -> Result<StringPadding> {
+        self.ensure_string()?;
+        match h5lock!(H5Tget_strpad(self.id())) {
+            H5T_str_t::H5T_STR_NULLTERM => Ok(StringPadding::NullTerminate),
+            H5T_str_t::H5T_STR_NULLPAD => Ok(StringPadding::NullPad),
+            H5T_str_t::H5T_STR_SPACEPAD => Ok(StringPadding::SpacePad),
+            _ => fail!("Invalid padding for string datatype"),
+        }
+    }
+
+    /// Set the padding of a string datatype.
+    pub fn set_strpad(&self, pad: StringPadding) -> Result<()> {
+        self.ensure_string()?;
+        h5call!(H5Tset_strpad(self.id(), pad.into())).and(Ok(()))
+    }
+
+    /// Get the bit layout (sign, exponent and mantissa fields) of a float datatype.
+    pub fn float_fields(&self) -> Result<FloatFields> {
+        self.ensure_float()?;
+        let (mut spos, mut epos, mut esize, mut mpos, mut msize) = (0, 0, 0, 0, 0);
+        h5try!(H5Tget_fields(self.id(), &mut spos, &mut epos, &mut esize, &mut mpos, &mut msize));
+        Ok(FloatFields { spos, epos, esize, mpos, msize })
+    }
+
+    /// Get the exponent bias of a float datatype.
+    pub fn ebias(&self) -> Result<usize> {
+        self.ensure_float()?;
+        // zero is a valid bias, so failures are detected via the error stack
+        h5call!(H5Tget_ebias(self.id()))
+    }
+
+    /// Get the mantissa normalization of a float datatype.
+    pub fn norm(&self) -> Result<FloatNormalization> {
+        self.ensure_float()?;
+        FloatNormalization::from_raw(h5lock!(H5Tget_norm(self.id())))
+    }
+
+    /// Get the padding of unused internal bits of a float datatype.
+    pub fn inpad(&self) -> Result<BitPadding> {
+        self.ensure_float()?;
+        BitPadding::from_raw(h5lock!(H5Tget_inpad(self.id())))
+    }
+
+    /// Sets the bit layout of a float datatype; the fields must fit within its precision.
+    pub fn set_float_fields(&self, fields: FloatFields) -> Result<()> {
+        let FloatFields { spos, epos, esize, mpos, msize } = fields;
+        self.modify_float(|id| h5call!(H5Tset_fields(id, spos, epos, esize, mpos, msize)))
+    }
+
+    /// Sets the exponent bias of a float datatype.
+    pub fn set_ebias(&self, ebias: usize) -> Result<()> {
+        self.modify_float(|id| h5call!(H5Tset_ebias(id, ebias)))
+    }
+
+    /// Sets the mantissa normalization of a float datatype.
+    pub fn set_norm(&self, norm: FloatNormalization) -> Result<()> {
+        self.modify_float(|id| h5call!(H5Tset_norm(id, norm.into())))
+    }
+
+    /// Sets the padding of unused internal bits of a float datatype.
+    pub fn set_inpad(&self, pad: BitPadding) -> Result<()> {
+        self.modify_float(|id| h5call!(H5Tset_inpad(id, pad.into())))
+    }
+
+    fn modify_float<F: FnOnce(hid_t) -> Result<herr_t>>(&self, func: F) -> Result<()> {
+        self.ensure_float()?;
+        h5lock!({
+            ensure!(h5try!(H5Tcommitted(self.id())) <= 0, "Cannot modify a committed datatype");
+            func(self.id()).and(Ok(()))
+        })
+    }
+
+    /// Returns the number of significant bits of an atomic datatype.
+    pub fn precision(&self) -> Result<usize> {
+        match h5lock!(H5Tget_precision(self.id())) {
+            0 => fail!("Unable to get precision of datatype"),
+            precision => Ok(precision),
+        }
+    }
+
+    /// Sets the number of significant bits of an atomic datatype, adjusting its size
+    /// if necessary.
+    pub fn set_precision(&self, precision: usize) -> Result<()> {
+        h5lock!({
+            ensure!(h5try!(H5Tcommitted(self.id())) <= 0, "Cannot modify a committed datatype");
+            h5call!(H5Tset_precision(self.id(), precision)).and(Ok(()))
+        })
+    }
+
+    fn is_standard_float(&self) -> bool {
+        [Self::from_type::<f32>(), Self::from_type::<f64>()]
+            .iter()
+            .any(|dtype| dtype.as_ref().map_or(false, |dtype| self.equal_ignoring_order(dtype)))
+    }
+
+    /// Compares two datatypes ignoring byte order: both are copied and converted to native
+    /// byte order (where applicable) before comparing them via `H5Tequal`.
+    ///
+    /// Note that `PartialEq` is strict and treats types of different byte order as distinct.
+    pub fn equal_ignoring_order(&self, other: &Self) -> bool {
+        let native = if cfg!(target_endian = "little") {
+            H5T_order_t::H5T_ORDER_LE
+        } else {
+            H5T_order_t::H5T_ORDER_BE
+        };
+        h5lock!({
+            let _e = silence_errors();
+            let normalize = |dtype: &Self| -> Result<Self> {
+                let copy = Self::from_id(h5try!(H5Tcopy(dtype.id())))?;
+                // types without a byte order (e.g. strings) are left as they are
+                H5Tset_order(copy.id(), native);
+                Ok(copy)
+            };
+            match (normalize(self), normalize(other)) {
+                (Ok(a), Ok(b)) => a == b,
+                _ => false,
+            }
+        })
+    }
+
+    /// Creates an empty compound datatype of the given size which members can then be
+    /// added to via `insert_member()`.
+    pub fn new_compound(size: usize) -> Result<Self> {
+        Self::from_id(h5try!(H5Tcreate(H5T_class_t::H5T_COMPOUND, size.max(1) as _)))
+    }
+
+    fn ensure_compound(&self) -> Result<()> {
+        ensure!(
+            h5lock!(H5Tget_class(self.id())) == H5T_class_t::H5T_COMPOUND,
+            "Expected a compound datatype"
+        );
+        Ok(())
+    }
+
+    /// Returns the number of members of a compound or enum datatype.
+    pub fn nmembers(&self) -> Result<usize> {
+        h5call!(H5Tget_nmembers(self.id())).map(|n| n as _)
+    }
+
+    /// Adds a member to a compound datatype, growing the datatype if the member
+    /// doesn't fit within its current size.
+    ///
+    /// Committed (named) datatypes cannot be modified.
+    pub fn insert_member(&self, name: &str, offset: usize, dtype: &Self) -> Result<()> {
+        self.ensure_compound()?;
+        h5lock!({
+            ensure!(h5try!(H5Tcommitted(self.id())) <= 0, "Cannot modify a committed datatype");
+            let name = to_cstring(name)?;
+            let end = offset + dtype.size();
+            if end > self.size() {
+                h5try!(H5Tset_size(self.id(), end as _));
+            }
+            h5try!(H5Tinsert(self.id(), name.as_ptr(), offset as _, dtype.id()));
+            Ok(())
+        })
+    }
+
+    /// Returns the datatype of a member of a compound datatype given its dotted path,
+    /// descending into nested compound members (e.g. `"inner.x"`).
+    pub fn member_by_path(&self, path: &str) -> Result<Self> {
+        h5lock!({
+            let mut dtype = self.clone();
+            let mut end = 0;
+            for name in path.split('.') {
+                let parent = &path[..end.max(1) - 1];
+                end += name.len() + 1;
+                ensure!(
+                    dtype.ensure_compound().is_ok(),
+                    "Expected a compound datatype at {:?} in member path {:?}",
+                    parent,
+                    path
+                );
+                let c_name = to_cstring(name)?;
+                let idx = {
+                    let _e = silence_errors();
+                    H5Tget_member_index(dtype.id(), c_name.as_ptr())
+                };
+                ensure!(idx >= 0, "Compound member not found: {:?}", &path[..end - 1]);
+                dtype = Self::from_id(h5try!(H5Tget_member_type(dtype.id(), idx as _)))?;
+            }
+            Ok(dtype)
+        })
+    }
+
+    /// Returns a copy of a compound datatype with all padding between members removed.
+    pub fn pack(&self) -> Result<Self> {
+        h5lock!({
+            let dtype = Self::from_id(h5try!(H5Tcopy(self.id())))?;
+            h5try!(H5Tpack(dtype.id()));
+            Ok(dtype)
+        })
+    }
+
+    /// Creates an array datatype with the given dimensions whose elements are of this type.
+    pub fn array_of(&self, dims: &[Ix]) -> Result<Self> {
+        ensure!(!dims.is_empty(), "array datatype must have at least one dimension");
+        let dims: Vec<hsize_t> = dims.iter().map(|&x| x as _).collect();
+        Self::from_id(h5try!(H5Tarray_create2(self.id(), dims.len() as _, dims.as_ptr())))
+    }
+
+    /// Creates a variable-length sequence datatype whose elements are of this type.
+    pub fn vlen_of(&self) -> Result<Self> {
+        Self::from_id(h5try!(H5Tvlen_create(self.id())))
+    }
+
+    /// Returns the base datatype of an array, variable-length sequence or enum datatype.
+    pub fn super_type(&self) -> Result<Self> {
+        Self::from_id(h5try!(H5Tget_super(self.id())))
+    }
+
+    fn ensure_enum(&self) -> Result<()> {
+        ensure!(
+            h5lock!(H5Tget_class(self.id())) == H5T_class_t::H5T_ENUM,
+            "Expected an enum datatype"
+        );
+        Ok(())
+    }
+
+    /// Returns the size and signedness of the base type of an enum datatype, and whether
+    /// its byte order differs from the native one (member values are in the base order).
+    fn enum_base(&self) -> Result<(usize, bool, bool)> {
+        self.ensure_enum()?;
+        let base = self.super_type()?;
+        let native = if cfg!(target_endian = "little") {
+            H5T_order_t::H5T_ORDER_LE
+        } else {
+            H5T_order_t::H5T_ORDER_BE
+        };
+        let swap = h5lock!(H5Tget_order(base.id())) != native && base.size() > 1;
+        Ok((base.size(), base.is_signed()?, swap))
+    }
+
+    fn enum_value_from_raw(&self, raw: [u8; 8]) -> Result<i64> {
+        let (size, signed, swap) = self.enum_base()?;
+        let mut b = raw;
+        if swap && size <= b.len() {
+            b[..size].reverse();
+        }
+        Ok(match (size, signed) {
+            (1, true) => i64::from(b[0] as i8),
+            (1, false) => i64::from(b[0]),
+            (2, true) => i64::from(i16::from_ne_bytes([b[0], b[1]])),
+            (2, false) => i64::from(u16::from_ne_bytes([b[0], b[1]])),
+            (4, true) => i64::from(i32::from_ne_bytes([b[0], b[1], b[2], b[3]])),
+            (4, false) => i64::from(u32::from_ne_bytes([b[0], b[1], b[2], b[3]])),
+            (8, _) => i64::from_ne_bytes(b),
+            _ => fail!("Invalid base type for enum datatype"),
+        })
+    }
+
+    fn enum_value_to_raw(&self, value: i64) -> Result<Vec<u8>> {
+        let (size, signed, swap) = self.enum_base()?;
+        let raw = match (size, signed) {
+            (1, true) => i8::try_from(value).ok().map(|v| v.to_ne_bytes().to_vec()),
+            (1, false) => u8::try_from(value).ok().map(|v| v.to_ne_bytes().to_vec()),
+            (2, true) => i16::try_from(value).ok().map(|v| v.to_ne_bytes().to_vec()),
+            (2, false) => u16::try_from(value).ok().map(|v| v.to_ne_bytes().to_vec()),
+            (4, true) => i32::try_from(value).ok().map(|v| v.to_ne_bytes().to_vec()),
+            (4, false) => u32::try_from(value).ok().map(|v| v.to_ne_bytes().to_vec()),
+            (8, _) => Some(value.to_ne_bytes().to_vec()),
+            _ => fail!("Invalid base type for enum datatype"),
+        };
+        let mut raw = match raw {
+            Some(raw) => raw,
+            None => fail!("Value out of range for enum datatype: {}", value),
+        };
+        if swap {
+            raw.reverse();
+        }
+        Ok(raw)
+    }
+
+    /// Returns the names and values of all members of an enum datatype.
+    pub fn enum_members(&self) -> Result<Vec<(String, i64)>> {
+        self.ensure_enum()?;
+        h5lock!({
+            let mut members = Vec::new();
+            for idx in 0..h5try!(H5Tget_nmembers(self.id())) as _ {
+                let mut raw = [0u8; 8];
+                h5try!(H5Tget_member_value(self.id(), idx, raw.as_mut_ptr() as *mut _));
+                let value = self.enum_value_from_raw(raw)?;
+                let name = H5Tget_member_name(self.id(), idx);
+                ensure!(!name.is_null(), "Unable to get the name of enum member {}", idx);
+                members.push((string_from_cstr(name), value));
+                libc::free(name as *mut _);
+            }
+            Ok(members)
+        })
+    }
+
+    /// Returns the value of an enum datatype member with the given name.
+    pub fn enum_value_of(&self, name: &str) -> Result<i64> {
+        self.ensure_enum()?;
+        let name = to_cstring(name)?;
+        let mut raw = [0u8; 8];
+        h5try!(H5Tenum_valueof(self.id(), name.as_ptr(), raw.as_mut_ptr() as *mut _));
+        self.enum_value_from_raw(raw)
+    }
+
+    /// Returns the name of an enum datatype member with the given value.
+    pub fn enum_name_of(&self, value: i64) -> Result<String> {
+        let raw = self.enum_value_to_raw(value)?;
+        let size = self.enum_members()?.iter().map(|(name, _)| name.len()).max().unwrap_or(0) + 1;
+        let mut buf = vec![0 as c_char; size];
+        h5try!(H5Tenum_nameof(
+            self.id(),
+            raw.as_ptr() as *const _,
+            buf.as_mut_ptr(),
+            buf.len() as _
+        ));
+        Ok(string_from_cstr(buf.as_ptr()))
+    }
+
+    /// Serializes the datatype definition into a portable sequence of bytes.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        h5lock!({
+            let mut len: size_t = 0;
+            h5try!(H5Tencode(self.id(), ptr::null_mut(), &mut len));
+            let mut buf = vec![0_u8; len];
+            h5try!(H5Tencode(self.id(), buf.as_mut_ptr() as *mut _, &mut len));
+            Ok(buf)
+        })
+    }
+
+    /// Reconstructs a datatype from the bytes produced by `encode()`.
+    pub fn decode(buf: &[u8]) -> Result<Self> {
+        ensure!(!buf.is_empty(), "cannot decode datatype from an empty buffer");
+        Self::from_id(h5try!(H5Tdecode(buf.as_ptr() as *const _)))
+    }
+
     pub fn conv_path<D>(&self, dst: D) -> Option<Conversion>
     where
         D: Borrow<Self>,
@@ -161,6 +871,19 @@ impl Datatype {
         Self::from_type::<T>().ok().map_or(false, |dtype| &dtype == self)
     }
 
+    /// Checks that the size of this datatype matches the in-memory size of `T`, so that
+    /// a buffer of `T` can be safely used for I/O with this datatype.
+    pub fn assert_layout_matches<T: H5Type>(&self) -> Result<()> {
+        let (mem_size, dtype_size) = (mem::size_of::<T>(), self.size());
+        ensure!(
+            mem_size == dtype_size,
+            "datatype size mismatch: {} bytes in memory, {} bytes in datatype",
+            mem_size,
+            dtype_size
+        );
+        Ok(())
+    }
+
     pub(crate) fn ensure_convertible(&self, dst: &Self, required: Conversion) -> Result<()> {
         // TODO: more detailed error messages after Debug/Display are implemented for Datatype
         if let Some(conv) = self.conv_path(dst) {
@@ -262,15 +985,97 @@ impl Datatype {
                     let base_dt = Self::from_id(H5Tget_super(id))?;
                     Ok(TD::VarLenArray(Box::new(base_dt.to_descriptor()?)))
                 }
+                H5T_REFERENCE => {
+                    if h5try!(H5Tequal(id, *H5T_STD_REF_DSETREG)) > 0 {
+                        Ok(TD::RegionReference)
+                    } else {
+                        Err("Unsupported reference datatype".into())
+                    }
+                }
                 _ => Err("Unsupported datatype class".into()),
             }
         })
     }
 
+    /// Returns a recursive description of the datatype regardless of its class.
+    pub fn describe_full(&self) -> Result<DatatypeDescription> {
+        use hdf5_sys::h5t::{H5T_class_t::*, H5T_sign_t::*};
+        use DatatypeDescription as DD;
+
+        h5lock!({
+            let id = self.id();
+            let size = h5try!(H5Tget_size(id)) as usize;
+            let order = || ByteOrder::from_raw(H5Tget_order(id));
+            let base = || -> Result<Box<DD>> {
+                Ok(Box::new(Self::from_id(h5try!(H5Tget_super(id)))?.describe_full()?))
+            };
+            match H5Tget_class(id) {
+                H5T_INTEGER => {
+                    let signed = match H5Tget_sign(id) {
+                        H5T_SGN_NONE => false,
+                        H5T_SGN_2 => true,
+                        _ => fail!("Invalid sign of integer datatype"),
+                    };
+                    Ok(DD::Integer { size, signed, order: order()? })
+                }
+                H5T_FLOAT => Ok(DD::Float { size, order: order()? }),
+                H5T_TIME => Ok(DD::Time { size, order: order()? }),
+                H5T_BITFIELD => Ok(DD::Bitfield { size, order: order()? }),
+                H5T_OPAQUE => Ok(DD::Opaque { size }),
+                H5T_REFERENCE => Ok(DD::Reference { size }),
+                H5T_STRING => {
+                    let is_variable = h5try!(H5Tis_variable_str(id)) == 1;
+                    let size = if is_variable { None } else { Some(size) };
+                    Ok(DD::String { size, cset: self.cset()? })
+                }
+                H5T_ENUM => Ok(DD::Enum { base: base()?, members: self.enum_members()? }),
+                H5T_COMPOUND => {
+                    let mut members = Vec::new();
+                    for idx in 0..h5try!(H5Tget_nmembers(id)) as _ {
+                        let name = H5Tget_member_name(id, idx);
+                        let offset = h5try!(H5Tget_member_offset(id, idx));
+                        let ty = Self::from_id(h5try!(H5Tget_member_type(id, idx)))?;
+                        members.push(CompoundMemberDescription {
+                            name: string_from_cstr(name),
+                            offset: offset as _,
+                            ty: ty.describe_full()?,
+                        });
+                        libc::free(name as *mut _);
+                    }
+                    Ok(DD::Compound { size, members })
+                }
+                H5T_ARRAY => {
+                    let ndims = h5try!(H5Tget_array_ndims(id));
+                    let mut dims: Vec<hsize_t> = vec![0; ndims as _];
+                    h5try!(H5Tget_array_dims2(id, dims.as_mut_ptr()));
+                    Ok(DD::Array { base: base()?, dims: dims.iter().map(|&d| d as _).collect() })
+                }
+                H5T_VLEN => Ok(DD::VarLen { base: base()? }),
+                _ => fail!("Unsupported datatype class"),
+            }
+        })
+    }
+
     pub fn from_type<T: H5Type>() -> Result<Self> {
         Self::from_descriptor(&<T as H5Type>::type_descriptor())
     }
 
+    /// Returns the datatype corresponding to `T` (shorthand for `Datatype::from_type::<T>()`).
+    pub fn of<T: H5Type>() -> Result<Self> {
+        Self::from_type::<T>()
+    }
+
+    /// Returns the native in-memory datatype HDF5 would use for `T`.
+    pub fn native_of<T: H5Type>() -> Result<Self> {
+        Self::from_type::<T>()?.native_type()
+    }
+
+    /// Returns the native in-memory datatype HDF5 would convert this datatype to when
+    /// reading it (e.g. a 4-byte native integer for a 24-bit big-endian integer).
+    pub fn native_type(&self) -> Result<Self> {
+        Self::from_id(h5try!(H5Tget_native_type(self.id(), H5T_direction_t::H5T_DIR_DEFAULT)))
+    }
+
     pub fn from_descriptor(desc: &TypeDescriptor) -> Result<Self> {
         use hdf5_types::TypeDescriptor as TD;
 
@@ -357,6 +1162,7 @@ impl Datatype {
                 }
                 TD::VarLenAscii => string_type(None, H5T_cset_t::H5T_CSET_ASCII),
                 TD::VarLenUnicode => string_type(None, H5T_cset_t::H5T_CSET_UTF8),
+                TD::RegionReference => Ok(h5try!(H5Tcopy(*H5T_STD_REF_DSETREG))),
             }
         });
 
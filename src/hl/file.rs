@@ -1,20 +1,49 @@
 use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::path::Path;
 
+use bitflags::bitflags;
+
 use hdf5_sys::h5f::{
     H5Fclose, H5Fcreate, H5Fflush, H5Fget_access_plist, H5Fget_create_plist, H5Fget_filesize,
-    H5Fget_freespace, H5Fget_intent, H5Fget_obj_count, H5Fget_obj_ids, H5Fopen, H5F_ACC_DEFAULT,
-    H5F_ACC_EXCL, H5F_ACC_RDONLY, H5F_ACC_RDWR, H5F_ACC_TRUNC, H5F_OBJ_ALL, H5F_OBJ_FILE,
-    H5F_SCOPE_LOCAL,
+    H5Fget_freespace, H5Fget_intent, H5Fget_obj_count, H5Fget_obj_ids, H5Fopen, H5Freopen,
+    H5F_ACC_EXCL, H5F_ACC_RDONLY, H5F_ACC_RDWR, H5F_ACC_TRUNC, H5F_OBJ_ALL, H5F_OBJ_ATTR,
+    H5F_OBJ_DATASET, H5F_OBJ_DATATYPE, H5F_OBJ_FILE, H5F_OBJ_GROUP, H5F_OBJ_LOCAL, H5F_SCOPE_LOCAL,
+};
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::{
+    h5f::{
+        H5F_info2_t, H5Fget_info2, H5Fstart_swmr_write, H5F_ACC_SWMR_READ, H5F_ACC_SWMR_WRITE,
+        H5F_LIBVER_LATEST,
+    },
+    h5p::H5Pset_libver_bounds,
 };
 
+#[cfg(hdf5_1_10_0)]
+use crate::hl::location::IndexHeapSize;
+#[cfg(hdf5_1_10_2)]
+use crate::hl::plist::file_access::LibVerBounds;
 use crate::hl::plist::{
     file_access::{FileAccess, FileAccessBuilder},
     file_create::{FileCreate, FileCreateBuilder},
 };
 use crate::internal_prelude::*;
 
+bitflags! {
+    /// Types of open objects to look for via `File::open_object_count()`.
+    pub struct ObjectTypeMask: c_uint {
+        const FILE = H5F_OBJ_FILE;
+        const DATASET = H5F_OBJ_DATASET;
+        const GROUP = H5F_OBJ_GROUP;
+        const DATATYPE = H5F_OBJ_DATATYPE;
+        const ATTR = H5F_OBJ_ATTR;
+        const ALL = H5F_OBJ_ALL;
+        /// Restrict the search to objects opened through this file handle.
+        const LOCAL = H5F_OBJ_LOCAL;
+    }
+}
+
 /// File opening mode.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum OpenMode {
@@ -28,6 +57,58 @@ pub enum OpenMode {
     CreateExcl,
     /// Open a file as read/write if exists, create otherwise.
     Append,
+    /// Open a file as read-only for single-writer-multiple-reader (SWMR) access.
+    ///
+    /// The file must have been written with the latest file format.
+    #[cfg(hdf5_1_10_0)]
+    SwmrRead,
+    /// Open a file as read/write for single-writer-multiple-reader (SWMR) access.
+    ///
+    /// The file must have been written with the latest file format; the library version
+    /// bounds of the file access property list are set to latest when opening.
+    #[cfg(hdf5_1_10_0)]
+    SwmrWrite,
+}
+
+/// Superblock, free-space manager and shared object header message information of a file.
+#[cfg(hdf5_1_10_0)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FileInfo {
+    /// Version of the superblock.
+    pub super_version: u32,
+    /// Size of the superblock, in bytes.
+    pub super_size: u64,
+    /// Size of the superblock extension, in bytes.
+    pub super_ext_size: u64,
+    /// Version of the free-space manager.
+    pub free_version: u32,
+    /// Size of the free-space manager metadata, in bytes.
+    pub free_meta_size: u64,
+    /// Amount of free space tracked by the free-space manager, in bytes.
+    pub free_space: u64,
+    /// Version of the shared object header message table.
+    pub sohm_version: u32,
+    /// Size of the shared object header message table header, in bytes.
+    pub sohm_header_size: u64,
+    /// Storage of the shared object header message indexes and heaps.
+    pub sohm_msgs: IndexHeapSize,
+}
+
+#[cfg(hdf5_1_10_0)]
+impl From<H5F_info2_t> for FileInfo {
+    fn from(info: H5F_info2_t) -> Self {
+        Self {
+            super_version: info.super_.version as _,
+            super_size: info.super_.super_size as _,
+            super_ext_size: info.super_.super_ext_size as _,
+            free_version: info.free.version as _,
+            free_meta_size: info.free.meta_size as _,
+            free_space: info.free.tot_space as _,
+            sohm_version: info.sohm.version as _,
+            sohm_header_size: info.sohm.hdr_size as _,
+            sohm_msgs: info.sohm.msgs_info.into(),
+        }
+    }
 }
 
 /// HDF5 file object.
@@ -71,6 +152,20 @@ impl Deref for File {
     }
 }
 
+impl PartialEq for File {
+    fn eq(&self, other: &Self) -> bool {
+        <Location as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for File {}
+
+impl Hash for File {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        <Location as Hash>::hash(self, state)
+    }
+}
+
 impl File {
     /// Opens a file as read-only, file must exist.
     pub fn open<P: AsRef<Path>>(filename: P) -> Result<Self> {
@@ -119,7 +214,8 @@ impl File {
 
     /// Returns true if the file was opened in a read-only mode.
     pub fn is_read_only(&self) -> bool {
-        h5get!(H5Fget_intent(self.id()): c_uint).unwrap_or(H5F_ACC_DEFAULT) != H5F_ACC_RDWR
+        // The intent may have other flags set in addition to RDWR (e.g. SWMR_WRITE).
+        h5get!(H5Fget_intent(self.id()): c_uint).map_or(true, |intent| intent & H5F_ACC_RDWR == 0)
     }
 
     /// Returns the userblock size in bytes (or 0 if the file handle is invalid).
@@ -127,11 +223,59 @@ impl File {
         h5lock!(self.fcpl().map(|p| p.userblock()).unwrap_or(0))
     }
 
+    /// Returns the library version bounds the file was opened with.
+    #[cfg(hdf5_1_10_2)]
+    pub fn libver_bounds(&self) -> LibVerBounds {
+        h5lock!(self.fapl().map(|p| p.libver_bounds()).unwrap_or_default())
+    }
+
+    /// Returns superblock, free-space and shared message information of the file.
+    #[cfg(hdf5_1_10_0)]
+    pub fn info(&self) -> Result<FileInfo> {
+        let mut info = H5F_info2_t::default();
+        h5call!(H5Fget_info2(self.id(), &mut info)).map(|_| info.into())
+    }
+
+    /// Returns the version of the superblock of the file (which depends on the library
+    /// version bounds and the features used at creation).
+    #[cfg(hdf5_1_10_0)]
+    pub fn superblock_version(&self) -> Result<u32> {
+        self.info().map(|info| info.super_version)
+    }
+
     /// Flushes the file to the storage medium.
     pub fn flush(&self) -> Result<()> {
         h5call!(H5Fflush(self.id(), H5F_SCOPE_LOCAL)).and(Ok(()))
     }
 
+    /// Returns a new, independent handle to the same file.
+    ///
+    /// Dropping either handle leaves the other one usable; note, however, that `close()`
+    /// invalidates all handles to the file, including reopened ones.
+    pub fn reopen(&self) -> Result<Self> {
+        Self::from_id(h5try!(H5Freopen(self.id())))
+    }
+
+    /// Switches a file opened as read/write into single-writer-multiple-reader (SWMR) mode.
+    ///
+    /// The file must have been created or opened with the latest library version bounds.
+    #[cfg(hdf5_1_10_0)]
+    pub fn start_swmr_write(&self) -> Result<()> {
+        h5call!(H5Fstart_swmr_write(self.id())).and(Ok(()))
+    }
+
+    /// Returns the number of open objects of the given types in the file (including
+    /// the file handles themselves if `ObjectTypeMask::FILE` is set).
+    pub fn open_object_count(&self, types: ObjectTypeMask) -> usize {
+        h5call!(H5Fget_obj_count(self.id(), types.bits())).unwrap_or(0) as _
+    }
+
+    /// Returns IDs of open objects of the given types in the file, excluding this file
+    /// handle. NOTE: these are borrowed references.
+    pub fn open_object_ids(&self, types: ObjectTypeMask) -> Vec<hid_t> {
+        self.get_obj_ids(types.bits())
+    }
+
     /// Returns objects IDs of the contained objects. NOTE: these are borrowed references.
     fn get_obj_ids(&self, types: c_uint) -> Vec<hid_t> {
         h5lock!({
@@ -250,6 +394,10 @@ impl FileBuilder {
             OpenMode::ReadWrite => H5F_ACC_RDWR,
             OpenMode::Create => H5F_ACC_TRUNC,
             OpenMode::CreateExcl | OpenMode::Append => H5F_ACC_EXCL,
+            #[cfg(hdf5_1_10_0)]
+            OpenMode::SwmrRead => H5F_ACC_RDONLY | H5F_ACC_SWMR_READ,
+            #[cfg(hdf5_1_10_0)]
+            OpenMode::SwmrWrite => H5F_ACC_RDWR | H5F_ACC_SWMR_WRITE,
         };
         let fname_ptr = filename.as_ptr();
         h5lock!({
@@ -258,6 +406,11 @@ impl FileBuilder {
                 OpenMode::Read | OpenMode::ReadWrite => {
                     File::from_id(h5try!(H5Fopen(fname_ptr, flags, fapl.id())))
                 }
+                #[cfg(hdf5_1_10_0)]
+                OpenMode::SwmrRead | OpenMode::SwmrWrite => {
+                    h5try!(H5Pset_libver_bounds(fapl.id(), H5F_LIBVER_LATEST, H5F_LIBVER_LATEST));
+                    File::from_id(h5try!(H5Fopen(fname_ptr, flags, fapl.id())))
+                }
                 _ => {
                     let fcpl = self.fcpl.finish()?;
                     File::from_id(h5try!(H5Fcreate(fname_ptr, flags, fcpl.id(), fapl.id())))
@@ -353,10 +506,76 @@ impl FileBuilder {
 
 #[cfg(test)]
 pub mod tests {
+    use crate::hl::file::ObjectTypeMask;
+    use crate::hl::plist::file_access::{Alignment, FileCloseDegree};
     use crate::internal_prelude::*;
     use std::fs;
     use std::io::{Read, Write};
 
+    #[test]
+    pub fn test_open_object_count() {
+        with_tmp_file(|file| {
+            file.create_group("a").unwrap();
+            file.new_dataset::<u32>().create("b", 1).unwrap();
+            let groups = ObjectTypeMask::GROUP;
+            assert_eq!(file.open_object_count(groups), 0);
+            assert!(file.open_object_count(ObjectTypeMask::FILE) >= 1);
+
+            let group = file.group("a").unwrap();
+            let ds = file.dataset("b").unwrap();
+            assert_eq!(file.open_object_count(groups), 1);
+            assert_eq!(file.open_object_count(groups | ObjectTypeMask::DATASET), 2);
+            assert_eq!(file.open_object_ids(groups), vec![group.id()]);
+            assert_eq!(file.open_object_ids(ObjectTypeMask::DATASET), vec![ds.id()]);
+
+            drop(group);
+            assert_eq!(file.open_object_count(groups), 0);
+            assert!(file.open_object_ids(groups).is_empty());
+            assert_eq!(file.open_object_count(ObjectTypeMask::DATASET), 1);
+        })
+    }
+
+    #[test]
+    pub fn test_filename_reopen() {
+        with_tmp_dir(|dir| {
+            let path = dir.join("foo.h5");
+            let file = File::create(&path).unwrap();
+            assert_eq!(file.filename(), path.to_str().unwrap());
+            let group = file.create_group("a").unwrap();
+            file.close();
+            assert_eq!(group.filename(), "");
+
+            let file = File::open(&path).unwrap();
+            assert_eq!(file.filename(), path.to_str().unwrap());
+            assert_eq!(file.group("a").unwrap().filename(), path.to_str().unwrap());
+
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.core_filebacked(false))
+                .create("logical.h5")
+                .unwrap();
+            assert_eq!(file.filename(), "logical.h5");
+            assert!(fs::metadata("logical.h5").is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_reopen() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            file.new_dataset::<u8>().create("foo", 3).unwrap().write_raw(&[1, 2, 3]).unwrap();
+            let reopened = file.reopen().unwrap();
+            assert_ne!(reopened.id(), file.id());
+            assert_eq!(reopened.filename(), file.filename());
+            drop(file);
+            assert!(reopened.is_valid());
+            assert_eq!(reopened.dataset("foo").unwrap().read_raw::<u8>().unwrap(), vec![1, 2, 3]);
+            reopened.create_group("bar").unwrap();
+            let other = reopened.reopen().unwrap();
+            reopened.close();
+            assert!(!other.is_valid());
+        })
+    }
+
     #[test]
     pub fn test_is_read_only() {
         with_tmp_path(|path| {
@@ -466,6 +685,12 @@ pub mod tests {
                 FileBuilder::new().with_fcpl(|p| p.userblock(1)).create(&path),
                 "userblock size is non-zero and less than 512"
             );
+            assert_err!(
+                FileBuilder::new().with_fcpl(|p| p.userblock(700)).create(&path),
+                "userblock size is not valid"
+            );
+            FileBuilder::new().with_fcpl(|p| p.userblock(1024)).create(&path).unwrap();
+            assert_eq!(File::open(&path).unwrap().userblock(), 1024);
             FileBuilder::new().with_fcpl(|p| p.userblock(512)).create(&path).unwrap();
             assert_eq!(File::open(&path).unwrap().userblock(), 512);
 
@@ -499,6 +724,89 @@ pub mod tests {
         })
     }
 
+    #[test]
+    #[cfg(hdf5_1_10_2)]
+    pub fn test_libver_bounds() {
+        use crate::file::LibraryVersion;
+        with_tmp_file(|file| {
+            assert_eq!(file.libver_bounds(), Default::default());
+        });
+        with_tmp_path(|path| {
+            let (low, high) = (LibraryVersion::Earliest, LibraryVersion::latest());
+            let file =
+                FileBuilder::new().with_fapl(|p| p.libver_bounds(low, high)).create(&path).unwrap();
+            assert_eq!(file.libver_bounds().low, low);
+            assert_eq!(file.libver_bounds().high, high);
+            file.close();
+            let (low, high) = (LibraryVersion::V18, LibraryVersion::V18);
+            let file =
+                FileBuilder::new().with_fapl(|p| p.libver_bounds(low, high)).open(&path).unwrap();
+            assert_eq!(file.libver_bounds().low, low);
+            assert_eq!(file.libver_bounds().high, high);
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_2)]
+    pub fn test_superblock_info() {
+        use crate::file::LibraryVersion;
+        with_tmp_dir(|dir| {
+            let (earliest, latest) = (LibraryVersion::Earliest, LibraryVersion::latest());
+            let old = FileBuilder::new()
+                .with_fapl(|p| p.libver_bounds(earliest, latest))
+                .create(dir.join("old.h5"))
+                .unwrap();
+            let new = FileBuilder::new()
+                .with_fapl(|p| p.libver_bounds(latest, latest))
+                .create(dir.join("new.h5"))
+                .unwrap();
+            assert_eq!(old.superblock_version().unwrap(), 0);
+            assert_eq!(new.superblock_version().unwrap(), 3);
+            let info = new.info().unwrap();
+            assert_eq!(info.super_version, 3);
+            assert!(info.super_size > 0);
+            assert!(old.info().unwrap().super_size > 0);
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_2)]
+    pub fn test_swmr() {
+        use crate::file::{LibraryVersion, OpenMode};
+        with_tmp_path(|path| {
+            let latest = LibraryVersion::latest();
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.libver_bounds(latest, latest))
+                .create(&path)
+                .unwrap();
+            file.new_dataset::<u32>().chunk(10).resizable(true).create("foo", 0).unwrap();
+            file.start_swmr_write().unwrap();
+            file.close();
+
+            let file = File::open_as(&path, OpenMode::SwmrWrite).unwrap();
+            assert!(!file.is_read_only());
+            file.close();
+
+            let reader = File::open_as(&path, OpenMode::SwmrRead).unwrap();
+            assert!(reader.is_read_only());
+            reader.dataset("foo").unwrap();
+        })
+    }
+
+    #[test]
+    pub fn test_alignment_meta_block_size() {
+        with_tmp_path(|path| {
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.alignment(2048, 1024 * 1024).meta_block_size(4096))
+                .create(&path)
+                .unwrap();
+            let fapl = file.fapl().unwrap();
+            assert_eq!(fapl.alignment().threshold, 2048);
+            assert_eq!(fapl.alignment().alignment, 1024 * 1024);
+            assert_eq!(fapl.meta_block_size(), 4096);
+        })
+    }
+
     #[test]
     pub fn test_close_automatic() {
         // File going out of scope should just close its own handle
@@ -525,6 +833,86 @@ pub mod tests {
         })
     }
 
+    #[test]
+    #[cfg(hdf5_1_10_1)]
+    pub fn test_paged_file_space() {
+        use crate::hl::plist::file_create::FileSpaceStrategy;
+
+        let strategy =
+            FileSpaceStrategy::FreeSpaceManager { paged: true, persist: true, threshold: 1 };
+        with_tmp_path(|path| {
+            let file = FileBuilder::new()
+                .with_fcpl(|p| p.file_space_strategy(strategy).file_space_page_size(4096))
+                .create(&path)
+                .unwrap();
+            file.new_dataset::<u32>().create("foo", 100).unwrap().write_raw(&[7; 100]).unwrap();
+            file.close();
+
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.page_buffer_size(8 * 4096, 0, 0))
+                .open(&path)
+                .unwrap();
+            let fcpl = file.fcpl().unwrap();
+            assert_eq!(fcpl.file_space_strategy(), strategy);
+            assert_eq!(fcpl.file_space_page_size(), 4096);
+            assert_eq!(file.fapl().unwrap().page_buffer_size().buf_size, 8 * 4096);
+            assert_eq!(file.dataset("foo").unwrap().read_raw::<u32>().unwrap(), vec![7; 100]);
+        })
+    }
+
+    #[test]
+    pub fn test_get_plists() {
+        with_tmp_path(|path| {
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.alignment(128, 4096))
+                .with_fcpl(|p| p.userblock(1024))
+                .create(&path)
+                .unwrap();
+            let fcpl = file.create_plist().unwrap();
+            assert_eq!(fcpl.class().unwrap(), PropertyListClass::FileCreate);
+            assert_eq!(fcpl.userblock(), 1024);
+            let fapl = file.access_plist().unwrap();
+            assert_eq!(fapl.class().unwrap(), PropertyListClass::FileAccess);
+            assert_eq!(fapl.alignment(), Alignment { threshold: 128, alignment: 4096 });
+            file.close();
+
+            let file = File::open(&path).unwrap();
+            assert_eq!(file.create_plist().unwrap().userblock(), 1024);
+            assert_eq!(file.access_plist().unwrap().alignment().alignment, 1);
+        })
+    }
+
+    #[test]
+    pub fn test_close_degree() {
+        with_tmp_path(|path| {
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.fclose_degree(FileCloseDegree::Strong))
+                .create(&path)
+                .unwrap();
+            assert_eq!(file.fapl().unwrap().fclose_degree(), FileCloseDegree::Strong);
+            let ds = file.new_dataset::<u8>().create("foo", 1).unwrap();
+            drop(file);
+            assert!(!ds.is_valid());
+
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.fclose_degree(FileCloseDegree::Strong))
+                .open(&path)
+                .unwrap();
+            let ds = file.dataset("foo").unwrap();
+            file.close();
+            assert!(!ds.is_valid());
+
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.fclose_degree(FileCloseDegree::Weak))
+                .open(&path)
+                .unwrap();
+            let ds = file.dataset("foo").unwrap();
+            drop(file);
+            assert!(ds.is_valid());
+            assert_eq!(ds.read_raw::<u8>().unwrap(), vec![0]);
+        })
+    }
+
     #[test]
     pub fn test_core_fd_non_filebacked() {
         with_tmp_path(|path| {
@@ -588,6 +976,49 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_split_fd() {
+        with_tmp_dir(|dir| {
+            let path = dir.join("data");
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.split_options(".meta", ".raw"))
+                .create(&path)
+                .unwrap();
+            let ds = file.new_dataset::<u32>().create("foo", 100).unwrap();
+            ds.write_raw(&(0..100).collect::<Vec<_>>()).unwrap();
+            file.close();
+            assert!(fs::metadata(dir.join("data.meta")).is_ok());
+            assert!(fs::metadata(dir.join("data.raw")).is_ok());
+            assert!(fs::metadata(&path).is_err());
+
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.split_options(".meta", ".raw"))
+                .open(&path)
+                .unwrap();
+            let data = file.dataset("foo").unwrap().read_raw::<u32>().unwrap();
+            assert_eq!(data, (0..100).collect::<Vec<_>>());
+        })
+    }
+
+    #[test]
+    pub fn test_family_fd() {
+        with_tmp_dir(|dir| {
+            let path = dir.join("data-%d.h5");
+            let file =
+                FileBuilder::new().with_fapl(|p| p.family_options(4096)).create(&path).unwrap();
+            let ds = file.new_dataset::<u64>().no_chunk().create("foo", 1024).unwrap();
+            ds.write_raw(&(0..1024).collect::<Vec<_>>()).unwrap();
+            file.close();
+            assert!(fs::metadata(dir.join("data-0.h5")).is_ok());
+            assert!(fs::metadata(dir.join("data-1.h5")).is_ok());
+
+            let file =
+                FileBuilder::new().with_fapl(|p| p.family_options(4096)).open(&path).unwrap();
+            let data = file.dataset("foo").unwrap().read_raw::<u64>().unwrap();
+            assert_eq!(data, (0..1024).collect::<Vec<_>>());
+        })
+    }
+
     #[test]
     pub fn test_debug() {
         with_tmp_dir(|dir| {
@@ -1,21 +1,46 @@
+use std::any::Any;
 use std::fmt::{self, Debug};
-use std::ops::Deref;
+use std::hash::{Hash, Hasher};
+use std::ops::{ControlFlow, Deref};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use bitflags::bitflags;
 
 use hdf5_sys::{
     h5::{hsize_t, H5_index_t, H5_iter_order_t},
-    h5d::H5Dopen2,
+    h5d::{H5Dcreate2, H5Dget_create_plist, H5Dopen2},
+    h5f::{H5Fmount, H5Funmount},
     h5g::{H5G_info_t, H5Gcreate2, H5Gget_info, H5Gopen2},
     h5l::{
-        H5L_info_t, H5L_iterate_t, H5Lcreate_hard, H5Lcreate_soft, H5Ldelete, H5Lexists,
-        H5Literate, H5Lmove, H5L_SAME_LOC,
+        H5L_info_t, H5L_iterate_t, H5L_type_t, H5Lcreate_hard, H5Lcreate_soft, H5Ldelete,
+        H5Lexists, H5Lget_val, H5Literate, H5Lmove, H5Lunpack_elink_val, H5Lvisit, H5L_SAME_LOC,
+    },
+    h5o::{
+        H5O_info_t, H5O_type_t, H5Ocopy, H5O_COPY_EXPAND_EXT_LINK_FLAG,
+        H5O_COPY_EXPAND_REFERENCE_FLAG, H5O_COPY_EXPAND_SOFT_LINK_FLAG,
+        H5O_COPY_PRESERVE_NULL_FLAG, H5O_COPY_SHALLOW_HIERARCHY_FLAG, H5O_COPY_WITHOUT_ATTR_FLAG,
+    },
+    h5p::{
+        H5Pcreate, H5Pset_copy_object, H5Pset_create_intermediate_group, H5Pset_est_link_info,
+        H5Pset_link_creation_order, H5Pset_link_phase_change, H5P_CRT_ORDER_INDEXED,
+        H5P_CRT_ORDER_TRACKED,
     },
-    h5p::{H5Pcreate, H5Pset_create_intermediate_group},
 };
 
-use crate::globals::H5P_LINK_CREATE;
+#[cfg(not(hdf5_1_10_3))]
+use hdf5_sys::h5o::H5Oget_info_by_name;
+#[cfg(hdf5_1_10_3)]
+use hdf5_sys::h5o::{H5Oget_info_by_name2, H5O_INFO_BASIC};
+
+use crate::globals::{H5P_GROUP_CREATE, H5P_LINK_CREATE, H5P_OBJECT_COPY};
+use crate::hl::plist::dataset_access::DatasetAccess;
 use crate::internal_prelude::*;
 
 /// Represents the HDF5 group object.
+///
+/// `File` dereferences to `Group` (its root group), so functions accepting `&Group`
+/// can be used with both files and groups.
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct Group(Handle);
@@ -56,18 +81,93 @@ impl Deref for Group {
     }
 }
 
+impl PartialEq for Group {
+    fn eq(&self, other: &Self) -> bool {
+        <Location as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for Group {}
+
+impl Hash for Group {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        <Location as Hash>::hash(self, state)
+    }
+}
+
+bitflags! {
+    /// Options controlling how objects are copied via `Group::copy_object_with()`.
+    pub struct ObjectCopyFlags: c_uint {
+        /// Copy only the immediate members of a group.
+        const SHALLOW_HIERARCHY = H5O_COPY_SHALLOW_HIERARCHY_FLAG;
+        /// Copy the objects pointed to by soft links instead of the links themselves.
+        const EXPAND_SOFT_LINK = H5O_COPY_EXPAND_SOFT_LINK_FLAG;
+        /// Copy the objects pointed to by external links instead of the links themselves.
+        const EXPAND_EXT_LINK = H5O_COPY_EXPAND_EXT_LINK_FLAG;
+        /// Copy the objects pointed to by object references.
+        const EXPAND_REFERENCE = H5O_COPY_EXPAND_REFERENCE_FLAG;
+        /// Copy objects without their attributes.
+        const WITHOUT_ATTR = H5O_COPY_WITHOUT_ATTR_FLAG;
+        /// Copy NULL messages (empty space in the object header).
+        const PRESERVE_NULL = H5O_COPY_PRESERVE_NULL_FLAG;
+    }
+}
+
+impl Default for ObjectCopyFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Index type used when iterating over links in a group.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IndexType {
+    /// Alphanumeric order of link names.
+    #[default]
+    Name,
+    /// Order in which links were created (must be tracked when creating the group).
+    CreationOrder,
+}
+
+impl From<IndexType> for H5_index_t {
+    fn from(index: IndexType) -> Self {
+        match index {
+            IndexType::Name => H5_index_t::H5_INDEX_NAME,
+            IndexType::CreationOrder => H5_index_t::H5_INDEX_CRT_ORDER,
+        }
+    }
+}
+
+/// Type of a link along with its unresolved target, as reported by `Group::visit_links()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// A hard link pointing directly to an object.
+    Hard,
+    /// A soft link holding a path (which may not resolve to any object).
+    Soft(String),
+    /// An external link holding a file name and a path within that file.
+    External { file: String, path: String },
+    /// A user-defined link.
+    UserDefined,
+}
+
 fn group_info(id: hid_t) -> Result<H5G_info_t> {
     let info: *mut H5G_info_t = &mut H5G_info_t::default();
     h5call!(H5Gget_info(id, info)).and(Ok(unsafe { *info }))
 }
 
-fn make_lcpl() -> Result<PropertyList> {
+pub(crate) fn make_lcpl() -> Result<PropertyList> {
     h5lock!({
         let lcpl = PropertyList::from_id(h5try!(H5Pcreate(*H5P_LINK_CREATE)))?;
         h5call!(H5Pset_create_intermediate_group(lcpl.id(), 1)).and(Ok(lcpl))
     })
 }
 
+fn link_exists(id: hid_t, name: &str) -> Result<bool> {
+    let name = to_cstring(name)?;
+    Ok(h5call!(H5Lexists(id, name.as_ptr(), H5P_DEFAULT))? > 0)
+}
+
 impl Group {
     /// Returns the number of objects in the container (or 0 if the container is invalid).
     pub fn len(&self) -> u64 {
@@ -82,17 +182,35 @@ impl Group {
     /// Create a new group in a file or group.
     pub fn create_group(&self, name: &str) -> Result<Self> {
         // TODO: &mut self?
-        h5lock!({
-            let lcpl = make_lcpl()?;
-            let name = to_cstring(name)?;
-            Self::from_id(h5try!(H5Gcreate2(
-                self.id(),
-                name.as_ptr(),
-                lcpl.id(),
-                H5P_DEFAULT,
-                H5P_DEFAULT
-            )))
-        })
+        self.new_group().create(name)
+    }
+
+    /// Creates a group and all of its missing parent groups; existing levels are opened.
+    ///
+    /// Returns handles to every level of the path in order, from the outermost group to
+    /// the innermost one.
+    pub fn create_group_all(&self, path: &str) -> Result<Vec<Self>> {
+        let mut prefix = if path.starts_with('/') { "/".to_owned() } else { "".to_owned() };
+        let mut groups = Vec::new();
+        for name in path.split('/').filter(|s| !s.is_empty()) {
+            if !prefix.is_empty() && !prefix.ends_with('/') {
+                prefix.push('/');
+            }
+            prefix.push_str(name);
+            let group = if link_exists(self.id(), &prefix)? {
+                self.group(&prefix)?
+            } else {
+                self.create_group(&prefix)?
+            };
+            groups.push(group);
+        }
+        ensure!(!groups.is_empty(), "invalid group path: {:?}", path);
+        Ok(groups)
+    }
+
+    /// Instantiates a new group builder.
+    pub fn new_group(&self) -> GroupBuilder {
+        GroupBuilder::new(self)
     }
 
     /// Opens an existing group in a file or group.
@@ -145,6 +263,87 @@ impl Group {
         .and(Ok(()))
     }
 
+    /// Moves a link to a new location, creating intermediate groups of `dst` as needed.
+    ///
+    /// Fails if a link already exists at `dst`; see `move_link_overwrite()`. Note: `src`
+    /// and `dst` are relative to the current object.
+    pub fn move_link(&self, src: &str, dst: &str) -> Result<()> {
+        h5lock!({
+            let lcpl = make_lcpl()?;
+            let src = to_cstring(src)?;
+            let dst = to_cstring(dst)?;
+            h5call!(H5Lmove(
+                self.id(),
+                src.as_ptr(),
+                H5L_SAME_LOC,
+                dst.as_ptr(),
+                lcpl.id(),
+                H5P_DEFAULT
+            ))
+            .and(Ok(()))
+        })
+    }
+
+    /// Same as `move_link()`, but replaces the link at `dst` if it already exists.
+    ///
+    /// The existing link is only removed once the move has succeeded; if the move fails,
+    /// both `src` and `dst` are left untouched.
+    pub fn move_link_overwrite(&self, src: &str, dst: &str) -> Result<()> {
+        h5lock!({
+            ensure!(self.link_exists(src), "unable to move link: name doesn't exist");
+            let (src_path, dst_path) = (src.trim_end_matches('/'), dst.trim_end_matches('/'));
+            ensure!(src_path != dst_path, "unable to move link: source and destination are equal");
+            ensure!(
+                !dst_path.starts_with(&format!("{}/", src_path)),
+                "unable to move link: destination is within the source"
+            );
+            if !self.link_exists(dst) {
+                return self.move_link(src, dst);
+            }
+            // Move the existing link aside so that it can be restored if the move fails.
+            let backup = (0..)
+                .map(|i| format!("{}.~{}", dst_path, i))
+                .find(|name| !self.link_exists(name))
+                .unwrap();
+            self.move_link(dst, &backup)?;
+            if let Err(err) = self.move_link(src, dst) {
+                self.move_link(&backup, dst)?;
+                return Err(err);
+            }
+            self.unlink(&backup)
+        })
+    }
+
+    /// Recursively copies the object at `src` (data and attributes) to `dst_path` within
+    /// `dst`, which may belong to a different file.
+    ///
+    /// Intermediate groups of `dst_path` are created as needed.
+    pub fn copy_object(&self, src: &str, dst: &Group, dst_path: &str) -> Result<()> {
+        self.copy_object_with(src, dst, dst_path, ObjectCopyFlags::default())
+    }
+
+    /// Same as `copy_object()`, but with explicit copy options.
+    pub fn copy_object_with(
+        &self, src: &str, dst: &Group, dst_path: &str, flags: ObjectCopyFlags,
+    ) -> Result<()> {
+        h5lock!({
+            let ocpypl = PropertyList::from_id(h5try!(H5Pcreate(*H5P_OBJECT_COPY)))?;
+            h5try!(H5Pset_copy_object(ocpypl.id(), flags.bits()));
+            let lcpl = make_lcpl()?;
+            let src = to_cstring(src)?;
+            let dst_path = to_cstring(dst_path)?;
+            h5call!(H5Ocopy(
+                self.id(),
+                src.as_ptr(),
+                dst.id(),
+                dst_path.as_ptr(),
+                ocpypl.id(),
+                lcpl.id()
+            ))
+            .and(Ok(()))
+        })
+    }
+
     /// Removes a link to an object from this file or group.
     pub fn unlink(&self, name: &str) -> Result<()> {
         // TODO: &mut self?
@@ -152,28 +351,155 @@ impl Group {
         h5call!(H5Ldelete(self.id(), name.as_ptr(), H5P_DEFAULT)).and(Ok(()))
     }
 
+    /// Removes a group along with everything below it, unlinking its members bottom-up
+    /// before unlinking the group itself.
+    ///
+    /// Only hard links to groups are descended into; soft and external links are removed
+    /// without touching their targets. Objects also linked from outside the subtree are
+    /// kept, although such groups end up empty since their members are unlinked as well.
+    pub fn remove_recursive(&self, path: &str) -> Result<()> {
+        extern "C" fn visit_callback(
+            _id: hid_t, name: *const c_char, _info: *const H5L_info_t, op_data: *mut c_void,
+        ) -> herr_t {
+            let names: &mut Vec<String> = unsafe { &mut *(op_data as *mut Vec<String>) };
+            names.push(string_from_cstr(name));
+            0
+        }
+
+        h5lock!({
+            let group = self.group(path)?;
+            let mut names: Vec<String> = Vec::new();
+            h5try!(H5Lvisit(
+                group.id(),
+                H5_index_t::H5_INDEX_NAME,
+                H5_iter_order_t::H5_ITER_INC,
+                Some(visit_callback),
+                &mut names as *mut _ as *mut c_void
+            ));
+            // links are visited top-down (a group before its members)
+            for name in names.iter().rev() {
+                group.unlink(name)?;
+            }
+            drop(group);
+            self.unlink(path)
+        })
+    }
+
+    /// Mounts the root group of the file `child` onto the existing group `name`, so that paths
+    /// beneath the mount point resolve into the child file until it's unmounted.
+    pub fn mount(&self, name: &str, child: &File) -> Result<()> {
+        let name = to_cstring(name)?;
+        h5call!(H5Fmount(self.id(), name.as_ptr(), child.id(), H5P_DEFAULT)).and(Ok(()))
+    }
+
+    /// Unmounts the file that was mounted onto the group `name`.
+    pub fn unmount(&self, name: &str) -> Result<()> {
+        let name = to_cstring(name)?;
+        h5call!(H5Funmount(self.id(), name.as_ptr())).and(Ok(()))
+    }
+
     /// Check if a link with a given name exists in this file or group.
+    ///
+    /// Each component of the path is checked in turn, so missing intermediate
+    /// groups result in `false` rather than an error.
     pub fn link_exists(&self, name: &str) -> bool {
         (|| -> Result<bool> {
-            let name = to_cstring(name)?;
-            Ok(h5call!(H5Lexists(self.id(), name.as_ptr(), H5P_DEFAULT))? > 0)
+            let mut path = if name.starts_with('/') { "/".to_owned() } else { String::new() };
+            let mut parts = name.split('/').filter(|part| !part.is_empty()).peekable();
+            if parts.peek().is_none() {
+                return link_exists(self.id(), name);
+            }
+            for part in parts {
+                if !path.is_empty() && !path.ends_with('/') {
+                    path.push('/');
+                }
+                path.push_str(part);
+                if !link_exists(self.id(), &path)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
         })()
         .unwrap_or(false)
     }
 
+    /// Check if this file or group has a direct child link with a given name.
+    pub fn contains(&self, name: &str) -> bool {
+        !name.is_empty() && !name.contains('/') && link_exists(self.id(), name).unwrap_or(false)
+    }
+
+    /// Returns the kind of the object at the given path (a group, a dataset or a named
+    /// datatype) without opening it; fails with `Error::NotFound` if there's no such object.
+    pub fn object_type(&self, name: &str) -> Result<ObjectKind> {
+        let name = to_cstring(name)?;
+        let mut info = H5O_info_t::default();
+        #[cfg(hdf5_1_10_3)]
+        h5try!(H5Oget_info_by_name2(
+            self.id(),
+            name.as_ptr(),
+            &mut info,
+            H5O_INFO_BASIC,
+            H5P_DEFAULT
+        ));
+        #[cfg(not(hdf5_1_10_3))]
+        h5try!(H5Oget_info_by_name(self.id(), name.as_ptr(), &mut info, H5P_DEFAULT));
+        match info.type_ {
+            H5O_type_t::H5O_TYPE_GROUP => Ok(ObjectKind::Group),
+            H5O_type_t::H5O_TYPE_DATASET => Ok(ObjectKind::Dataset),
+            H5O_type_t::H5O_TYPE_NAMED_DATATYPE => Ok(ObjectKind::Datatype),
+            object_type => fail!("unsupported object type: {:?}", object_type),
+        }
+    }
+
     /// Instantiates a new dataset builder.
     pub fn new_dataset<T: H5Type>(&self) -> DatasetBuilder<T> {
         DatasetBuilder::<T>::new(self)
     }
 
+    /// Creates a new dataset with the given shape, reusing the datatype and the creation
+    /// properties (layout, chunking, filters, fill value) of an existing dataset.
+    pub fn new_dataset_like<D: Dimension>(
+        &self, name: &str, template: &Dataset, shape: D,
+    ) -> Result<Dataset> {
+        h5lock!({
+            let dcpl = PropertyList::from_id(h5try!(H5Dget_create_plist(template.id())))?;
+            let datatype = template.dtype()?;
+            let dataspace = Dataspace::try_new(&shape, template.is_resizable())?;
+            let lcpl = make_lcpl()?;
+            let name = to_cstring(name)?;
+            Dataset::from_id(h5try!(H5Dcreate2(
+                self.id(),
+                name.as_ptr(),
+                datatype.id(),
+                dataspace.id(),
+                lcpl.id(),
+                dcpl.id(),
+                H5P_DEFAULT
+            )))
+        })
+    }
+
     /// Opens an existing dataset in the file or group.
     pub fn dataset(&self, name: &str) -> Result<Dataset> {
         let name = to_cstring(name)?;
         Dataset::from_id(h5try!(H5Dopen2(self.id(), name.as_ptr(), H5P_DEFAULT)))
     }
 
+    /// Opens an existing dataset in the file or group using the given access properties
+    /// (e.g. the prefix and file access properties used to resolve external links).
+    pub fn dataset_with_access(&self, name: &str, dapl: &DatasetAccess) -> Result<Dataset> {
+        let name = to_cstring(name)?;
+        Dataset::from_id(h5try!(H5Dopen2(self.id(), name.as_ptr(), dapl.id())))
+    }
+
     /// Returns names of all the members in the group, non-recursively.
     pub fn member_names(&self) -> Result<Vec<String>> {
+        self.member_names_by(IndexType::Name)
+    }
+
+    /// Returns names of all the members in the group, non-recursively, ordered by
+    /// the given index.
+    pub fn member_names_by(&self, index: IndexType) -> Result<Vec<String>> {
         extern "C" fn members_callback(
             _id: hid_t, name: *const c_char, _info: *const H5L_info_t, op_data: *mut c_void,
         ) -> herr_t {
@@ -191,7 +517,7 @@ impl Group {
 
         h5call!(H5Literate(
             self.id(),
-            H5_index_t::H5_INDEX_NAME,
+            index.into(),
             H5_iter_order_t::H5_ITER_INC,
             iteration_position,
             callback_fn,
@@ -200,12 +526,231 @@ impl Group {
 
         Ok(result)
     }
+
+    /// Iterates over the members of the group, non-recursively, in the order of the given
+    /// index, calling `f` with the name of each member.
+    ///
+    /// The iteration stops as soon as `f` returns `ControlFlow::Break`, in which case the
+    /// break value is returned; otherwise, `None` is returned once all members are visited.
+    /// If `f` panics, the iteration is aborted and the panic is resumed once control is
+    /// back from the library.
+    pub fn iter_members<B, F>(&self, index: IndexType, mut f: F) -> Result<Option<B>>
+    where
+        F: FnMut(&str) -> ControlFlow<B>,
+    {
+        struct IterData<'a, B, F> {
+            f: &'a mut F,
+            result: Option<B>,
+            panic: Option<Box<dyn Any + Send>>,
+        }
+
+        extern "C" fn iter_callback<B, F>(
+            _id: hid_t, name: *const c_char, _info: *const H5L_info_t, op_data: *mut c_void,
+        ) -> herr_t
+        where
+            F: FnMut(&str) -> ControlFlow<B>,
+        {
+            let data: &mut IterData<B, F> = unsafe { &mut *(op_data as *mut IterData<B, F>) };
+            let name = string_from_cstr(name);
+            // a panic must not unwind through the library, so it's re-raised after iterating
+            match panic::catch_unwind(AssertUnwindSafe(|| (data.f)(&name))) {
+                Ok(ControlFlow::Continue(())) => 0,
+                Ok(ControlFlow::Break(value)) => {
+                    data.result = Some(value);
+                    1 // Short-circuit success
+                }
+                Err(payload) => {
+                    data.panic = Some(payload);
+                    -1
+                }
+            }
+        }
+
+        let mut data = IterData { f: &mut f, result: None, panic: None };
+        let iteration_position: *mut hsize_t = &mut { 0 as u64 };
+        let res = h5call!(H5Literate(
+            self.id(),
+            index.into(),
+            H5_iter_order_t::H5_ITER_INC,
+            iteration_position,
+            Some(iter_callback::<B, F>),
+            &mut data as *mut _ as *mut c_void
+        ));
+        if let Some(payload) = data.panic.take() {
+            panic::resume_unwind(payload);
+        }
+        res?;
+        Ok(data.result)
+    }
+
+    /// Recursively visits all links below this group (including dangling soft and external
+    /// links) in name order, calling `f` with the path of each link relative to this group
+    /// and its unresolved target.
+    pub fn visit_links<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&str, &LinkTarget),
+    {
+        extern "C" fn visit_callback(
+            _id: hid_t, name: *const c_char, info: *const H5L_info_t, op_data: *mut c_void,
+        ) -> herr_t {
+            let links: &mut Vec<(String, H5L_info_t)> =
+                unsafe { &mut *(op_data as *mut Vec<(String, H5L_info_t)>) };
+            links.push((string_from_cstr(name), unsafe { *info }));
+            0
+        }
+
+        let links = h5lock!({
+            let mut links: Vec<(String, H5L_info_t)> = Vec::new();
+            h5try!(H5Lvisit(
+                self.id(),
+                H5_index_t::H5_INDEX_NAME,
+                H5_iter_order_t::H5_ITER_INC,
+                Some(visit_callback),
+                &mut links as *mut _ as *mut c_void
+            ));
+            links
+                .into_iter()
+                .map(|(name, mut info)| {
+                    let target = match info.type_ {
+                        H5L_type_t::H5L_TYPE_HARD => LinkTarget::Hard,
+                        H5L_type_t::H5L_TYPE_SOFT | H5L_type_t::H5L_TYPE_EXTERNAL => {
+                            let size = unsafe { *info.u.val_size() };
+                            self.link_value(&name, info.type_, size)?
+                        }
+                        _ => LinkTarget::UserDefined,
+                    };
+                    Ok((name, target))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+        for (name, target) in &links {
+            f(name, target);
+        }
+        Ok(())
+    }
+
+    fn link_value(&self, name: &str, link_type: H5L_type_t, size: usize) -> Result<LinkTarget> {
+        let c_name = to_cstring(name)?;
+        let mut buf = vec![0 as c_char; size.max(1)];
+        h5try!(H5Lget_val(
+            self.id(),
+            c_name.as_ptr(),
+            buf.as_mut_ptr() as *mut _,
+            buf.len(),
+            H5P_DEFAULT
+        ));
+        if link_type == H5L_type_t::H5L_TYPE_SOFT {
+            *buf.last_mut().unwrap() = 0;
+            return Ok(LinkTarget::Soft(string_from_cstr(buf.as_ptr())));
+        }
+        let (mut file, mut path) = (ptr::null(), ptr::null());
+        h5try!(H5Lunpack_elink_val(
+            buf.as_ptr() as *const _,
+            buf.len(),
+            ptr::null_mut(),
+            &mut file,
+            &mut path
+        ));
+        ensure!(!file.is_null() && !path.is_null(), "invalid external link value: {}", name);
+        Ok(LinkTarget::External { file: string_from_cstr(file), path: string_from_cstr(path) })
+    }
+}
+
+/// Group builder allowing to customize group creation properties.
+#[derive(Clone)]
+pub struct GroupBuilder {
+    parent: Result<Handle>,
+    track_creation_order: bool,
+    est_link_info: Option<(u32, u32)>,
+    link_phase_change: Option<(u32, u32)>,
+}
+
+impl GroupBuilder {
+    /// Create a new group builder and bind it to the parent container.
+    pub fn new(parent: &Group) -> Self {
+        h5lock!({
+            // Store the reference to the parent handle and try to increase its reference count.
+            let handle = Handle::try_new(parent.id());
+            if let Ok(ref handle) = handle {
+                handle.incref();
+            }
+
+            Self {
+                parent: handle,
+                track_creation_order: false,
+                est_link_info: None,
+                link_phase_change: None,
+            }
+        })
+    }
+
+    /// Track and index the creation order of links in the group.
+    pub fn track_creation_order(&mut self, track_creation_order: bool) -> &mut Self {
+        self.track_creation_order = track_creation_order;
+        self
+    }
+
+    /// Set the estimated number of links and the average link name length, used to
+    /// preallocate space for links stored in compact form.
+    pub fn est_link_info(&mut self, est_num_entries: u32, est_name_len: u32) -> &mut Self {
+        self.est_link_info = Some((est_num_entries, est_name_len));
+        self
+    }
+
+    /// Set the thresholds for switching between compact and dense link storage: the
+    /// maximum number of links in compact form and the minimum number in dense form.
+    pub fn link_phase_change(&mut self, max_compact: u32, min_dense: u32) -> &mut Self {
+        self.link_phase_change = Some((max_compact, min_dense));
+        self
+    }
+
+    fn make_gcpl(&self) -> Result<PropertyList> {
+        h5lock!({
+            let gcpl = PropertyList::from_id(h5try!(H5Pcreate(*H5P_GROUP_CREATE)))?;
+            if self.track_creation_order {
+                let flags = H5P_CRT_ORDER_TRACKED | H5P_CRT_ORDER_INDEXED;
+                h5try!(H5Pset_link_creation_order(gcpl.id(), flags));
+            }
+            if let Some((est_num_entries, est_name_len)) = self.est_link_info {
+                h5try!(H5Pset_est_link_info(gcpl.id(), est_num_entries as _, est_name_len as _));
+            }
+            if let Some((max_compact, min_dense)) = self.link_phase_change {
+                h5try!(H5Pset_link_phase_change(gcpl.id(), max_compact as _, min_dense as _));
+            }
+            Ok(gcpl)
+        })
+    }
+
+    /// Create the group and link it into the file structure.
+    pub fn create(&self, name: &str) -> Result<Group> {
+        h5lock!({
+            let parent = try_ref_clone!(self.parent);
+            let lcpl = make_lcpl()?;
+            let gcpl = self.make_gcpl()?;
+            let name = to_cstring(name)?;
+            Group::from_id(h5try!(H5Gcreate2(
+                parent.id(),
+                name.as_ptr(),
+                lcpl.id(),
+                gcpl.id(),
+                H5P_DEFAULT
+            )))
+        })
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
+    use std::ops::ControlFlow;
+    use std::panic::{self, AssertUnwindSafe};
+
+    use hdf5_sys::h5l::H5Lcreate_external;
+
+    use crate::filters::gzip_available;
     use crate::internal_prelude::*;
 
+    use super::{IndexType, LinkTarget, ObjectCopyFlags};
+
     #[test]
     pub fn test_debug() {
         with_tmp_file(|file| {
@@ -241,6 +786,44 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_file_or_group() {
+        fn populate(parent: &Group) -> Result<Dataset> {
+            parent.create_group("sub")?;
+            parent.new_dataset::<u8>().create("sub/data", 1)
+        }
+
+        with_tmp_file(|file| {
+            let group = file.create_group("a").unwrap();
+            assert_eq!(populate(&file).unwrap().name(), "/sub/data");
+            assert_eq!(populate(&group).unwrap().name(), "/a/sub/data");
+            assert!(file.link_exists("sub") && group.link_exists("sub"));
+            assert!(populate(&file).is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_create_group_all() {
+        with_tmp_file(|file| {
+            let groups = file.create_group_all("/x/y/z").unwrap();
+            let names: Vec<_> = groups.iter().map(|g| g.name()).collect();
+            assert_eq!(names, vec!["/x", "/x/y", "/x/y/z"]);
+
+            file.group("x").unwrap().set_comment("existing").unwrap();
+            let groups = file.group("x").unwrap().create_group_all("y/w").unwrap();
+            let names: Vec<_> = groups.iter().map(|g| g.name()).collect();
+            assert_eq!(names, vec!["/x/y", "/x/y/w"]);
+            let groups = file.create_group_all("x//y/").unwrap();
+            assert_eq!(groups.len(), 2);
+            assert_eq!(groups[0].comment(), Some("existing".to_owned()));
+            assert_eq!(file.group("x/y").unwrap().len(), 2);
+
+            file.new_dataset::<u32>().create("x/d", 1).unwrap();
+            assert_err!(file.create_group_all("x/d/e"), "unable to open group");
+            assert_err!(file.create_group_all("/"), "invalid group path");
+        })
+    }
+
     #[test]
     pub fn test_clone() {
         with_tmp_file(|file| {
@@ -349,6 +932,83 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_link_exists_intermediate() {
+        with_tmp_file(|file| {
+            file.create_group("a/b").unwrap();
+            assert!(file.link_exists("/a/b"));
+            assert!(file.link_exists("/a/b/"));
+            assert!(!file.link_exists("/a/x"));
+            assert!(!file.link_exists("/a/x/y"));
+            assert!(!file.link_exists("x/y/z"));
+            let group = file.group("a").unwrap();
+            assert!(group.link_exists("b"));
+            assert!(group.link_exists("/a/b"));
+            assert!(!group.link_exists("a/b"));
+        })
+    }
+
+    #[test]
+    pub fn test_object_type() {
+        with_tmp_file(|file| {
+            file.create_group("a/b").unwrap();
+            file.new_dataset::<u32>().create("a/c", 3).unwrap();
+            let group = file.group("a").unwrap();
+            assert_eq!(file.object_type("a").unwrap(), ObjectKind::Group);
+            assert_eq!(file.object_type("a/b").unwrap(), ObjectKind::Group);
+            assert_eq!(file.object_type("/a/c").unwrap(), ObjectKind::Dataset);
+            assert_eq!(group.object_type("c").unwrap(), ObjectKind::Dataset);
+            assert_eq!(file.object_type("/").unwrap(), ObjectKind::Group);
+
+            file.link_soft("a/c", "d").unwrap();
+            assert_eq!(file.object_type("d").unwrap(), ObjectKind::Dataset);
+            file.link_soft("x", "dangling").unwrap();
+            match file.object_type("dangling") {
+                Err(Error::NotFound(_)) => (),
+                res => panic!("expected NotFound, got {:?}", res),
+            }
+            match group.object_type("x") {
+                Err(Error::NotFound(_)) => (),
+                res => panic!("expected NotFound, got {:?}", res),
+            }
+        })
+    }
+
+    #[test]
+    pub fn test_mount() {
+        with_tmp_dir(|dir| {
+            let child = File::create(dir.join("child.h5")).unwrap();
+            child.new_dataset::<i32>().create("data", 3).unwrap().write_raw(&[1, 2, 3]).unwrap();
+            let file = File::create(dir.join("parent.h5")).unwrap();
+            let group = file.create_group("mnt").unwrap();
+
+            file.mount("mnt", &child).unwrap();
+            assert_eq!(
+                file.dataset("/mnt/data").unwrap().read_raw::<i32>().unwrap(),
+                vec![1, 2, 3]
+            );
+            assert!(group.link_exists("data"));
+            assert_err!(file.mount("mnt", &child), "unable to mount file");
+            assert_err!(file.mount("missing", &child), "unable to mount file");
+
+            file.unmount("mnt").unwrap();
+            assert!(!file.link_exists("mnt/data"));
+            assert_err!(file.unmount("mnt"), "unable to unmount file");
+        })
+    }
+
+    #[test]
+    pub fn test_contains() {
+        with_tmp_file(|file| {
+            file.create_group("a/b").unwrap();
+            assert!(file.contains("a"));
+            assert!(!file.contains("b"));
+            assert!(!file.contains("a/b"));
+            assert!(!file.contains(""));
+            assert!(file.group("a").unwrap().contains("b"));
+        })
+    }
+
     #[test]
     pub fn test_relink() {
         with_tmp_file(|file| {
@@ -366,6 +1026,207 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_move_link() {
+        with_tmp_file(|file| {
+            file.create_group("a/old").unwrap().set_comment("moved").unwrap();
+            file.move_link("a/old", "b/new").unwrap();
+            assert!(!file.link_exists("a/old"));
+            assert!(file.link_exists("a"));
+            assert_eq!(file.group("/b/new").unwrap().comment(), Some("moved".to_owned()));
+            assert_err!(file.move_link("a/old", "c"), "unable to move link");
+
+            file.create_group("a/other").unwrap();
+            assert_err!(file.move_link("a/other", "b/new"), "unable to move link");
+            assert_eq!(file.group("b/new").unwrap().comment(), Some("moved".to_owned()));
+            file.move_link_overwrite("a/other", "b/new").unwrap();
+            assert!(!file.link_exists("a/other"));
+            assert_eq!(file.group("b/new").unwrap().comment(), None);
+            assert_err!(
+                file.move_link_overwrite("a/other", "b/new"),
+                "unable to move link: name doesn't exist"
+            );
+            assert!(file.link_exists("b/new"));
+
+            file.group("b/new").unwrap().set_comment("kept").unwrap();
+            assert_err!(
+                file.move_link_overwrite("b/new", "b/new/"),
+                "unable to move link: source and destination are equal"
+            );
+            assert_eq!(file.group("b/new").unwrap().comment(), Some("kept".to_owned()));
+
+            file.create_group("p/q").unwrap().set_comment("inner").unwrap();
+            assert_err!(
+                file.move_link_overwrite("p", "p/q"),
+                "unable to move link: destination is within the source"
+            );
+            // Not caught by comparing paths, so the failed move must be rolled back.
+            assert!(file.move_link_overwrite("/p", "p/q").is_err());
+            assert_eq!(file.group("p/q").unwrap().comment(), Some("inner".to_owned()));
+            assert_eq!(file.group("p").unwrap().member_names().unwrap(), vec!["q"]);
+            assert!(file.move_link_overwrite("b/new", "/b/new").is_err());
+            assert_eq!(file.group("b/new").unwrap().comment(), Some("kept".to_owned()));
+            assert_eq!(file.group("b").unwrap().member_names().unwrap(), vec!["new"]);
+        })
+    }
+
+    #[test]
+    pub fn test_copy_object() {
+        with_tmp_file(|src| {
+            with_tmp_file(|dst| {
+                let ds = src.new_dataset::<i32>().create("a/data", 3).unwrap();
+                ds.write_raw(&[1, 2, 3]).unwrap();
+                ds.new_attr::<u8>().create("attr", ()).unwrap().write_scalar(&7).unwrap();
+
+                src.copy_object("a/data", &dst, "b/copy").unwrap();
+                let copy = dst.dataset("b/copy").unwrap();
+                assert_eq!(copy.read_raw::<i32>().unwrap(), vec![1, 2, 3]);
+                assert_eq!(copy.attr("attr").unwrap().read_scalar::<u8>().unwrap(), 7);
+                assert_err!(src.copy_object("a/data", &dst, "b/copy"), "unable to copy object");
+                assert_err!(src.copy_object("missing", &dst, "c"), "unable to copy object");
+
+                src.copy_object_with("a/data", &dst, "bare", ObjectCopyFlags::WITHOUT_ATTR)
+                    .unwrap();
+                let bare = dst.dataset("bare").unwrap();
+                assert_eq!(bare.read_raw::<i32>().unwrap(), vec![1, 2, 3]);
+                assert!(!bare.has_attr("attr"));
+
+                src.link_soft("a/data", "soft").unwrap();
+                src.copy_object("a", &src, "a2").unwrap();
+                src.copy_object_with("/", &dst, "root", ObjectCopyFlags::EXPAND_SOFT_LINK).unwrap();
+                assert!(dst.dataset("root/soft").unwrap().is_dataset());
+                assert!(dst.dataset("root/a2/data").is_ok());
+            })
+        })
+    }
+
+    #[test]
+    pub fn test_remove_recursive() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u8>().no_chunk().create("/a/b/c/data", 100_000).unwrap();
+            ds.write_raw(&vec![1; 100_000]).unwrap();
+            drop(ds);
+            file.group("a/b").unwrap().link_soft("/keep", "soft").unwrap();
+            let a = file.group("a").unwrap();
+            let (other, root) = (to_cstring("other.h5").unwrap(), to_cstring("/").unwrap());
+            let ext = to_cstring("ext").unwrap();
+            let (o, r, e) = (other.as_ptr(), root.as_ptr(), ext.as_ptr());
+            h5call!(H5Lcreate_external(o, r, a.id(), e, H5P_DEFAULT, H5P_DEFAULT)).unwrap();
+            drop(a);
+            file.new_dataset::<u8>().create("keep", 10).unwrap().write_raw(&[2; 10]).unwrap();
+            file.link_hard("a/b/c", "shared").unwrap();
+            file.flush().unwrap();
+            let free_space = file.free_space();
+
+            file.remove_recursive("/a").unwrap();
+            assert!(!file.link_exists("a"));
+            assert_eq!(file.member_names().unwrap(), vec!["keep", "shared"]);
+            assert!(file.group("shared").unwrap().is_empty());
+            assert_eq!(file.dataset("keep").unwrap().read_raw::<u8>().unwrap(), vec![2; 10]);
+            file.flush().unwrap();
+            assert!(file.free_space() > free_space);
+
+            assert_err!(file.remove_recursive("a"), "unable to open group");
+            file.new_dataset::<u8>().create("x", 1).unwrap();
+            assert_err!(file.remove_recursive("x"), "unable to open group");
+        })
+    }
+
+    #[test]
+    pub fn test_visit_links() {
+        with_tmp_file(|file| {
+            file.new_dataset::<u8>().create("a/b/data", 1).unwrap();
+            file.group("a").unwrap().link_soft("/missing/path", "dangling").unwrap();
+            file.link_soft("/a/b", "soft").unwrap();
+            let a = file.group("a").unwrap();
+            let (other, root) = (to_cstring("other.h5").unwrap(), to_cstring("/x").unwrap());
+            let ext = to_cstring("ext").unwrap();
+            let (o, r, e) = (other.as_ptr(), root.as_ptr(), ext.as_ptr());
+            h5call!(H5Lcreate_external(o, r, a.id(), e, H5P_DEFAULT, H5P_DEFAULT)).unwrap();
+            drop(a);
+
+            let mut links = Vec::new();
+            file.visit_links(|name, target| links.push((name.to_owned(), target.clone()))).unwrap();
+            assert_eq!(
+                links,
+                vec![
+                    ("a".to_owned(), LinkTarget::Hard),
+                    ("a/b".to_owned(), LinkTarget::Hard),
+                    ("a/b/data".to_owned(), LinkTarget::Hard),
+                    ("a/dangling".to_owned(), LinkTarget::Soft("/missing/path".into())),
+                    (
+                        "a/ext".to_owned(),
+                        LinkTarget::External { file: "other.h5".into(), path: "/x".into() }
+                    ),
+                    ("soft".to_owned(), LinkTarget::Soft("/a/b".into())),
+                ]
+            );
+
+            let mut names = Vec::new();
+            file.group("a/b").unwrap().visit_links(|name, _| names.push(name.to_owned())).unwrap();
+            assert_eq!(names, vec!["data"]);
+        })
+    }
+
+    #[test]
+    pub fn test_iter_members() {
+        with_tmp_file(|file| {
+            for name in &["a", "b", "c", "d"] {
+                file.create_group(name).unwrap();
+            }
+
+            let mut visited = Vec::new();
+            let found = file
+                .iter_members(IndexType::Name, |name| {
+                    visited.push(name.to_owned());
+                    if name == "b" {
+                        ControlFlow::Break(format!("found {}", name))
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                })
+                .unwrap();
+            assert_eq!(found, Some("found b".to_owned()));
+            assert_eq!(visited, vec!["a", "b"]);
+
+            visited.clear();
+            let found = file
+                .iter_members(IndexType::Name, |name| {
+                    visited.push(name.to_owned());
+                    ControlFlow::<()>::Continue(())
+                })
+                .unwrap();
+            assert_eq!(found, None);
+            assert_eq!(visited, vec!["a", "b", "c", "d"]);
+        })
+    }
+
+    #[test]
+    pub fn test_iter_members_panic() {
+        with_tmp_file(|file| {
+            for name in &["a", "b", "c"] {
+                file.create_group(name).unwrap();
+            }
+
+            let mut visited = Vec::new();
+            let res = panic::catch_unwind(AssertUnwindSafe(|| {
+                file.iter_members(IndexType::Name, |name| {
+                    visited.push(name.to_owned());
+                    if name == "b" {
+                        panic!("boom");
+                    }
+                    ControlFlow::<()>::Continue(())
+                })
+            }));
+            let payload = res.unwrap_err();
+            assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"));
+            assert_eq!(visited, vec!["a", "b"]);
+
+            // the group is still usable afterwards
+            assert_eq!(file.member_names().unwrap(), vec!["a", "b", "c"]);
+        })
+    }
+
     #[test]
     pub fn test_unlink() {
         with_tmp_file(|file| {
@@ -385,6 +1246,31 @@ pub mod tests {
         });
     }
 
+    #[test]
+    pub fn test_new_dataset_like() {
+        with_tmp_file(|file| {
+            let mut builder = file.new_dataset::<i16>();
+            builder.chunk((10, 10)).shuffle(true).fill_value(-1);
+            if gzip_available() {
+                builder.gzip(4);
+            }
+            let template = builder.create("template", (100, 100)).unwrap();
+
+            let ds = file.new_dataset_like("a/like", &template, (20, 30)).unwrap();
+            assert_eq!(ds.shape(), vec![20, 30]);
+            assert_eq!(ds.chunks(), Some(vec![10, 10]));
+            assert_eq!(ds.filters(), template.filters());
+            assert!(ds.filters().get_shuffle());
+            assert_eq!(ds.fill_value::<i16>().unwrap(), Some(-1));
+            assert_eq!(ds.dtype().unwrap(), template.dtype().unwrap());
+            assert_eq!(ds.is_resizable(), false);
+
+            let template = file.new_dataset::<u8>().resizable(true).create("r", 5).unwrap();
+            assert!(file.new_dataset_like("r2", &template, 3).unwrap().is_resizable());
+            assert_err!(file.new_dataset_like("r2", &template, 3), "unable to create dataset");
+        })
+    }
+
     #[test]
     pub fn test_get_member_names() {
         with_tmp_file(|file| {
@@ -400,4 +1286,43 @@ pub mod tests {
             assert_eq!(file.member_names().unwrap(), vec!["a", "b"]);
         })
     }
+
+    #[test]
+    pub fn test_creation_order() {
+        with_tmp_file(|file| {
+            let group = file.new_group().track_creation_order(true).create("x").unwrap();
+            for name in &["z", "a", "m"] {
+                group.create_group(name).unwrap();
+            }
+            assert_eq!(group.member_names().unwrap(), vec!["a", "m", "z"]);
+            assert_eq!(
+                group.member_names_by(IndexType::CreationOrder).unwrap(),
+                vec!["z", "a", "m"]
+            );
+
+            let group = file.create_group("y").unwrap();
+            group.create_group("b").unwrap();
+            assert!(group.member_names_by(IndexType::CreationOrder).is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_link_storage_tuning() {
+        with_tmp_file(|file| {
+            let mut builder = file.new_group();
+            builder.est_link_info(10000, 8).link_phase_change(16, 12);
+            let group = builder.create("a").unwrap();
+            let mut names: Vec<_> = (0..2000).map(|i| format!("m{:05}", i)).collect();
+            for name in &names {
+                group.create_group(name).unwrap();
+            }
+            names.sort();
+            assert_eq!(group.len(), 2000);
+            assert_eq!(group.member_names().unwrap(), names);
+            assert!(group.contains("m01234"));
+
+            assert!(file.new_group().link_phase_change(4, 10).create("b").is_err());
+            assert!(!file.link_exists("b"));
+        })
+    }
 }
@@ -1,13 +1,32 @@
+use std::cell::RefCell;
 use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::ptr;
 
 use hdf5_sys::{
+    h5::{H5_ih_info_t, H5_index_t, H5_iter_order_t},
+    h5a::{H5A_info_t, H5A_operator2_t, H5Adelete, H5Aexists, H5Aiterate2, H5Aopen},
     h5f::H5Fget_name,
     h5i::{H5Iget_file_id, H5Iget_name},
-    h5o::{H5Oget_comment, H5Oset_comment},
+    h5o::{H5O_info_t, H5Oget_comment, H5Oset_comment},
 };
 
+#[cfg(not(hdf5_1_10_3))]
+use hdf5_sys::h5o::H5Oget_info;
+#[cfg(hdf5_1_10_3)]
+use hdf5_sys::h5o::{H5Oget_info2, H5O_INFO_BASIC, H5O_INFO_HDR, H5O_INFO_META_SIZE};
+
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::{
+    h5d::{H5Dflush, H5Drefresh},
+    h5g::{H5Gflush, H5Grefresh},
+    h5t::{H5Tflush, H5Trefresh},
+};
+
+use hdf5_types::VarLenUnicode;
+
+use crate::hl::attribute::{Attribute, AttributeBuilder};
 use crate::internal_prelude::*;
 
 /// Named location (file, group, dataset, named datatype).
@@ -47,6 +66,71 @@ impl Deref for Location {
     }
 }
 
+/// Named objects compare equal if they refer to the same object in the same open file
+/// (see `Location::token()`), no matter which handle or path they were opened through.
+///
+/// Handles whose token can't be retrieved (e.g. invalid ones, or handles to objects of a
+/// closed file) only compare equal to themselves and their clones. Closing the file also
+/// changes the hash of its objects, so such keys have to be looked up again afterwards.
+impl PartialEq for Location {
+    fn eq(&self, other: &Self) -> bool {
+        let _e = silence_errors();
+        match (self.token(), other.token()) {
+            (Ok(token), Ok(other_token)) => token == other_token,
+            (Err(_), Err(_)) => self.handle().ptr_eq(other.handle()),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Location {}
+
+impl Hash for Location {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let _e = silence_errors();
+        self.token().ok().hash(state)
+    }
+}
+
+/// Opaque identity of an object within an open file which stays the same no matter which
+/// handle or path the object has been opened through.
+///
+/// With the supported library versions the token is derived from the file number and the
+/// address of the object header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Token {
+    fileno: u64,
+    addr: u64,
+}
+
+/// Storage used by an index (B-tree) together with its heap, in bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IndexHeapSize {
+    pub index_size: u64,
+    pub heap_size: u64,
+}
+
+impl From<H5_ih_info_t> for IndexHeapSize {
+    fn from(info: H5_ih_info_t) -> Self {
+        Self { index_size: info.index_size as _, heap_size: info.heap_size as _ }
+    }
+}
+
+/// Storage footprint of the file-format metadata of an object.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NativeInfo {
+    /// Total space taken by the object header, in bytes.
+    pub header_size: u64,
+    /// Free space within the object header, in bytes.
+    pub header_free: u64,
+    /// Number of messages in the object header.
+    pub header_nmesgs: u32,
+    /// Storage of the links of a group or of the chunk index of a dataset.
+    pub obj: IndexHeapSize,
+    /// Storage of the attributes (only if they are stored densely).
+    pub attr: IndexHeapSize,
+}
+
 impl Location {
     /// Returns the name of the object within the file, or empty string if the object doesn't
     /// have a name (e.g., an anonymous dataset).
@@ -73,7 +157,7 @@ impl Location {
         comment.and_then(|c| if c.is_empty() { None } else { Some(c) })
     }
 
-    /// Set or the commment attached to the named object.
+    /// Set or the commment attached to the named object; an empty string clears it.
     pub fn set_comment(&self, comment: &str) -> Result<()> {
         // TODO: &mut self?
         let comment = to_cstring(comment)?;
@@ -85,12 +169,270 @@ impl Location {
         // TODO: &mut self?
         h5call!(H5Oset_comment(self.id(), ptr::null_mut())).and(Ok(()))
     }
+
+    /// Returns the token identifying the named object (for attributes, the object they are
+    /// attached to).
+    pub fn token(&self) -> Result<Token> {
+        let info = self.object_info(false)?;
+        Ok(Token { fileno: info.fileno as _, addr: info.addr as _ })
+    }
+
+    /// Returns the storage sizes of the object header and of the indices and heaps used by
+    /// the named object (for attributes, the object they are attached to).
+    pub fn native_info(&self) -> Result<NativeInfo> {
+        let info = self.object_info(true)?;
+        Ok(NativeInfo {
+            header_size: info.hdr.space.total as _,
+            header_free: info.hdr.space.free as _,
+            header_nmesgs: info.hdr.nmesgs as _,
+            obj: info.meta_size.obj.into(),
+            attr: info.meta_size.attr.into(),
+        })
+    }
+
+    fn object_info(&self, with_metadata: bool) -> Result<H5O_info_t> {
+        let mut info = H5O_info_t::default();
+        #[cfg(hdf5_1_10_3)]
+        {
+            let fields = if with_metadata {
+                H5O_INFO_BASIC | H5O_INFO_HDR | H5O_INFO_META_SIZE
+            } else {
+                H5O_INFO_BASIC
+            };
+            h5try!(H5Oget_info2(self.id(), &mut info, fields));
+        }
+        #[cfg(not(hdf5_1_10_3))]
+        {
+            let _ = with_metadata;
+            h5try!(H5Oget_info(self.id(), &mut info));
+        }
+        Ok(info)
+    }
+
+    /// Instantiates a new attribute builder.
+    pub fn new_attr<T: H5Type>(&self) -> AttributeBuilder<T> {
+        AttributeBuilder::<T>::new(self)
+    }
+
+    /// Opens an existing attribute attached to the named object.
+    pub fn attr(&self, name: &str) -> Result<Attribute> {
+        let name = to_cstring(name)?;
+        Attribute::from_id(h5try!(H5Aopen(self.id(), name.as_ptr(), H5P_DEFAULT)))
+    }
+
+    /// Returns names of all the attributes attached to the named object.
+    pub fn attr_names(&self) -> Result<Vec<String>> {
+        extern "C" fn attributes_callback(
+            _id: hid_t, attr_name: *const c_char, _info: *const H5A_info_t, op_data: *mut c_void,
+        ) -> herr_t {
+            let other_data: &mut Vec<String> = unsafe { &mut *(op_data as *mut Vec<String>) };
+
+            other_data.push(string_from_cstr(attr_name));
+
+            0 // Continue iteration
+        }
+
+        let callback_fn: H5A_operator2_t = Some(attributes_callback);
+        let iteration_position: *mut hsize_t = &mut { 0 as u64 };
+        let mut result: Vec<String> = Vec::new();
+        let other_data: *mut c_void = &mut result as *mut _ as *mut c_void;
+
+        h5call!(H5Aiterate2(
+            self.id(),
+            H5_index_t::H5_INDEX_NAME,
+            H5_iter_order_t::H5_ITER_INC,
+            iteration_position,
+            callback_fn,
+            other_data
+        ))?;
+
+        Ok(result)
+    }
+
+    /// Check if an attribute with a given name is attached to the named object.
+    pub fn has_attr(&self, name: &str) -> bool {
+        (|| -> Result<bool> {
+            let name = to_cstring(name)?;
+            Ok(h5call!(H5Aexists(self.id(), name.as_ptr()))? > 0)
+        })()
+        .unwrap_or(false)
+    }
+
+    /// Deletes an attribute attached to the named object.
+    pub fn delete_attr(&self, name: &str) -> Result<()> {
+        // TODO: &mut self?
+        let name = to_cstring(name)?;
+        h5call!(H5Adelete(self.id(), name.as_ptr())).and(Ok(()))
+    }
+
+    /// Attaches a scalar variable-length UTF-8 string attribute to the named object,
+    /// replacing an existing attribute with the same name.
+    pub fn set_string_attr(&self, name: &str, value: &str) -> Result<()> {
+        let value: VarLenUnicode =
+            value.parse().map_err(|_| format!("null byte in attribute value: {:?}", value))?;
+        h5lock!({
+            if self.has_attr(name) {
+                self.delete_attr(name)?;
+            }
+            self.new_attr::<VarLenUnicode>().create(name, ())?.write_scalar(&value)
+        })
+    }
+
+    /// Reads a scalar string attribute attached to the named object, returning `None`
+    /// if there's no attribute with such name.
+    pub fn string_attr(&self, name: &str) -> Result<Option<String>> {
+        if !self.has_attr(name) {
+            return Ok(None);
+        }
+        let value = self.attr(name)?.read_scalar::<VarLenUnicode>()?;
+        Ok(Some(value.as_str().to_owned()))
+    }
+
+    /// Flushes all buffers associated with a dataset, group or named datatype to disk,
+    /// without flushing the rest of the file (useful for SWMR writers).
+    #[cfg(hdf5_1_10_0)]
+    pub fn flush(&self) -> Result<()> {
+        let id = self.id();
+        match self.id_type() {
+            H5I_DATASET => h5call!(H5Dflush(id)),
+            H5I_GROUP => h5call!(H5Gflush(id)),
+            H5I_DATATYPE => h5call!(H5Tflush(id)),
+            id_type => fail!("unable to flush object of type {:?}", id_type),
+        }
+        .and(Ok(()))
+    }
+
+    /// Refreshes the metadata of a dataset, group or named datatype, discarding cached
+    /// information (useful for SWMR readers to pick up changes made by a writer).
+    #[cfg(hdf5_1_10_0)]
+    pub fn refresh(&self) -> Result<()> {
+        let id = self.id();
+        match self.id_type() {
+            H5I_DATASET => h5call!(H5Drefresh(id)),
+            H5I_GROUP => h5call!(H5Grefresh(id)),
+            H5I_DATATYPE => h5call!(H5Trefresh(id)),
+            id_type => fail!("unable to refresh object of type {:?}", id_type),
+        }
+        .and(Ok(()))
+    }
+}
+
+/// Wrapper around a named object (group, dataset, named datatype) which caches the name
+/// of the object after it's first resolved, so that repeated `name()` calls don't query
+/// the library again.
+///
+/// The cached name is not updated if the object is moved or renamed; use `clear_name()`
+/// to force a fresh lookup. It is dropped automatically once the object becomes invalid.
+pub struct NamedObject<T> {
+    obj: T,
+    name: RefCell<Option<String>>,
+}
+
+impl<T: ObjectClass> NamedObject<T> {
+    /// Wraps an object; the name is looked up lazily.
+    pub fn new(obj: T) -> Self {
+        Self { obj, name: RefCell::new(None) }
+    }
+
+    /// Returns the name of the object, looking it up on the first call only (or an empty
+    /// string if the handle is invalid).
+    pub fn name(&self) -> String {
+        let id = self.obj.handle().id();
+        if !is_valid_user_id(id) {
+            self.clear_name();
+            return "".into();
+        }
+        let mut name = self.name.borrow_mut();
+        if name.is_none() {
+            *name = h5lock!(get_h5_str(|m, s| H5Iget_name(id, m, s))).ok();
+        }
+        name.clone().unwrap_or_else(|| "".into())
+    }
+
+    /// Drops the cached name so that it's looked up again on the next `name()` call.
+    pub fn clear_name(&self) {
+        self.name.replace(None);
+    }
+
+    /// Unwraps the underlying object.
+    pub fn into_inner(self) -> T {
+        self.obj
+    }
+}
+
+impl<T> Deref for NamedObject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.obj
+    }
+}
+
+impl<T: Clone> Clone for NamedObject<T> {
+    fn clone(&self) -> Self {
+        Self { obj: self.obj.clone(), name: self.name.clone() }
+    }
+}
+
+impl<T: Debug> Debug for NamedObject<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.obj.fmt(f)
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
+    use std::collections::HashSet;
+    use std::ops::Deref;
+
     use crate::internal_prelude::*;
 
+    use super::{IndexHeapSize, NamedObject};
+
+    #[test]
+    pub fn test_string_attr() {
+        with_tmp_file(|file| {
+            let group = file.create_group("a").unwrap();
+            assert_eq!(group.string_attr("description").unwrap(), None);
+            group.set_string_attr("description", "hello").unwrap();
+            assert_eq!(group.string_attr("description").unwrap(), Some("hello".to_owned()));
+            group.set_string_attr("description", "{\"key\": \"värde\"}").unwrap();
+            assert_eq!(group.string_attr("description").unwrap().unwrap(), "{\"key\": \"värde\"}");
+            assert_eq!(group.attr_names().unwrap(), vec!["description"]);
+            assert!(group.attr("description").unwrap().is_scalar());
+            assert_err!(group.set_string_attr("x", "a\0b"), "null byte in attribute value");
+            assert!(!group.has_attr("x"));
+
+            group.new_attr::<u32>().create("num", ()).unwrap();
+            assert!(group.string_attr("num").is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_named_object() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let group = NamedObject::new(file.create_group("a/b").unwrap());
+            assert_eq!(group.name(), "/a/b");
+            assert_eq!(group.name(), group.name());
+            assert_eq!(group.name(), group.deref().name());
+            assert_eq!(group.len(), 0);
+
+            file.move_link("a/b", "c").unwrap();
+            assert_eq!(group.deref().name(), "/c");
+            assert_eq!(group.name(), "/a/b");
+            group.clear_name();
+            assert_eq!(group.name(), "/c");
+
+            let ds = NamedObject::new(file.new_dataset::<u8>().create("d", 1).unwrap());
+            assert_eq!(ds.clone().name(), "/d");
+            file.close();
+            assert_eq!(ds.name(), "");
+            assert!(!ds.is_valid());
+            assert_eq!(ds.into_inner().name(), "");
+        })
+    }
+
     #[test]
     pub fn test_filename() {
         with_tmp_path(|path| {
@@ -112,6 +454,85 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_token() {
+        with_tmp_file(|file| {
+            let a = file.create_group("a").unwrap();
+            let b = file.create_group("b").unwrap();
+            file.link_hard("a", "c").unwrap();
+            let token = a.token().unwrap();
+            assert_eq!(file.group("a").unwrap().token().unwrap(), token);
+            assert_eq!(file.group("c").unwrap().token().unwrap(), token);
+            assert_ne!(b.token().unwrap(), token);
+            assert_eq!(file.token().unwrap(), file.group("/").unwrap().token().unwrap());
+            assert_ne!(file.token().unwrap(), token);
+
+            with_tmp_file(|other| {
+                assert_ne!(other.token().unwrap(), file.token().unwrap());
+            })
+        })
+    }
+
+    #[test]
+    pub fn test_eq_hash() {
+        with_tmp_file(|file| {
+            let a = file.create_group("a").unwrap();
+            file.link_hard("a", "c").unwrap();
+            let ds = file.new_dataset::<u8>().create("ds", 1).unwrap();
+
+            let (a1, a2) = (file.group("a").unwrap(), file.group("c").unwrap());
+            assert_ne!(a1.id(), a2.id());
+            assert_eq!(a1, a2);
+            assert_eq!(a, a1);
+            assert_ne!(a1, file.group("/").unwrap());
+            assert_eq!(ds, file.dataset("ds").unwrap());
+
+            let mut set = HashSet::new();
+            set.insert(a1);
+            set.insert(a2);
+            assert_eq!(set.len(), 1);
+            set.insert(file.group("/").unwrap());
+            assert_eq!(set.len(), 2);
+
+            let files: HashSet<File> = vec![file.clone(), a.file().unwrap()].into_iter().collect();
+            assert_eq!(files.len(), 1);
+        });
+
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let (a, b) = (file.create_group("a").unwrap(), file.create_group("b").unwrap());
+            let a_copy = a.clone();
+            file.close();
+            assert!(!a.is_valid() && !b.is_valid());
+            assert_eq!(a, a_copy);
+            assert_ne!(a, b);
+        })
+    }
+
+    #[test]
+    pub fn test_native_info() {
+        with_tmp_file(|file| {
+            let group = file.create_group("a").unwrap();
+            let empty = group.native_info().unwrap();
+            for i in 0..20 {
+                group.create_group(&format!("g{}", i)).unwrap();
+            }
+            let info = group.native_info().unwrap();
+            assert!(info.header_size > 0);
+            assert!(info.header_nmesgs > 0);
+            assert!(info.obj.index_size > 0);
+            assert!(
+                info.obj.index_size + info.obj.heap_size
+                    > empty.obj.index_size + empty.obj.heap_size
+            );
+            assert_eq!(info.attr, IndexHeapSize::default());
+
+            let ds = file.new_dataset::<u8>().chunk(10).create("ds", 100).unwrap();
+            ds.write_raw(&[1; 100]).unwrap();
+            assert!(ds.native_info().unwrap().obj.index_size > 0);
+        })
+    }
+
     #[test]
     pub fn test_comment() {
         with_tmp_file(|file| {
@@ -122,4 +543,55 @@ pub mod tests {
             assert!(file.comment().is_none());
         })
     }
+
+    #[test]
+    pub fn test_group_comment() {
+        with_tmp_file(|file| {
+            let group = file.create_group("a").unwrap();
+            assert!(group.comment().is_none());
+            group.set_comment("héllo").unwrap();
+            assert_eq!(file.group("a").unwrap().comment().unwrap(), "héllo");
+            group.set_comment("").unwrap();
+            assert!(group.comment().is_none());
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_0)]
+    pub fn test_flush() {
+        with_tmp_file(|file| {
+            let writer = file.new_dataset::<u32>().resizable(true).create("foo", 2).unwrap();
+            let reader = file.dataset("foo").unwrap();
+            writer.resize(3).unwrap();
+            writer.write_raw(&[1, 2, 3]).unwrap();
+            writer.flush().unwrap();
+            reader.refresh().unwrap();
+            assert_eq!(reader.read_raw::<u32>().unwrap(), vec![1, 2, 3]);
+
+            file.create_group("a").unwrap().flush().unwrap();
+            let attr = file.new_attr::<u8>().create("b", ()).unwrap();
+            assert_err!(attr.flush(), "unable to flush object");
+        })
+    }
+
+    /// Only checks that refreshing succeeds for datasets and groups and fails for files: both
+    /// handles share the metadata cache of one open file, so the reader sees the writer's
+    /// changes whether or not `refresh()` discards anything (that needs a separate process).
+    #[test]
+    #[cfg(hdf5_1_10_0)]
+    pub fn test_refresh() {
+        with_tmp_file(|file| {
+            let reader = file.new_dataset::<u32>().resizable(true).create("foo", 2).unwrap();
+            let writer = file.dataset("foo").unwrap();
+            writer.resize(5).unwrap();
+            writer.write_raw(&[1, 2, 3, 4, 5]).unwrap();
+            file.flush().unwrap();
+            reader.refresh().unwrap();
+            assert_eq!(reader.shape(), vec![5]);
+            assert_eq!(reader.read_raw::<u32>().unwrap(), vec![1, 2, 3, 4, 5]);
+
+            file.create_group("a").unwrap().refresh().unwrap();
+            assert_err!(file.refresh(), "unable to refresh object");
+        })
+    }
 }
@@ -1,3 +1,4 @@
+pub mod attribute;
 pub mod container;
 pub mod dataset;
 pub mod datatype;
@@ -6,16 +7,22 @@ pub mod group;
 pub mod location;
 pub mod object;
 pub mod plist;
+pub mod reference;
 pub mod space;
 
 pub use self::{
+    attribute::{Attribute, AttributeBuilder},
     container::{Container, Reader, Writer},
     dataset::{Dataset, DatasetBuilder},
-    datatype::{Conversion, Datatype},
+    datatype::{
+        BitPadding, ByteOrder, CharSet, CompoundMemberDescription, Conversion, Datatype,
+        DatatypeClass, DatatypeDescription, FloatFields, FloatNormalization, StringPadding,
+    },
     file::{File, FileBuilder, OpenMode},
-    group::Group,
-    location::Location,
-    object::Object,
+    group::{Group, GroupBuilder},
+    location::{IndexHeapSize, Location, NamedObject, NativeInfo, Token},
+    object::{Object, ObjectKind, WeakObject},
     plist::PropertyList,
+    reference::RegionReference,
     space::Dataspace,
 };
@@ -1,7 +1,9 @@
 use std::fmt::{self, Debug};
+use std::marker::PhantomData;
 
 use hdf5_sys::h5i::H5Iget_ref;
 
+use crate::handle::WeakHandle;
 use crate::internal_prelude::*;
 
 /// Any HDF5 object that can be referenced through an identifier.
@@ -24,6 +26,35 @@ impl ObjectClass for Object {
     // TODO: short_repr()
 }
 
+/// Kind of the HDF5 object an identifier refers to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ObjectKind {
+    File,
+    Group,
+    Datatype,
+    Dataspace,
+    Dataset,
+    Attribute,
+    PropertyList,
+    /// Any other identifier type (including invalid identifiers).
+    Other(H5I_type_t),
+}
+
+impl From<H5I_type_t> for ObjectKind {
+    fn from(id_type: H5I_type_t) -> Self {
+        match id_type {
+            H5I_FILE => ObjectKind::File,
+            H5I_GROUP => ObjectKind::Group,
+            H5I_DATATYPE => ObjectKind::Datatype,
+            H5I_DATASPACE => ObjectKind::Dataspace,
+            H5I_DATASET => ObjectKind::Dataset,
+            H5I_ATTR => ObjectKind::Attribute,
+            H5I_GENPROP_LST => ObjectKind::PropertyList,
+            _ => ObjectKind::Other(id_type),
+        }
+    }
+}
+
 impl Debug for Object {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.debug_fmt(f)
@@ -54,16 +85,98 @@ impl Object {
     pub fn id_type(&self) -> H5I_type_t {
         get_id_type(self.id())
     }
+
+    /// Converts the object into a concrete object type like `Group` or `Dataset`, passing
+    /// on its handle; fails if the type of the identifier doesn't match.
+    pub fn downcast<T: ObjectClass>(self) -> Result<T> {
+        h5lock!({
+            let id = self.id();
+            if T::is_valid_id_type(self.id_type()) {
+                let obj = unsafe { self.cast::<T>() };
+                obj.validate().map(|_| obj)
+            } else {
+                Err(From::from(format!("Invalid {} id: {}", T::NAME, id)))
+            }
+        })
+    }
+
+    /// Returns the kind of the object.
+    pub fn object_kind(&self) -> ObjectKind {
+        self.id_type().into()
+    }
+
+    /// Returns `true` if the object is a file.
+    pub fn is_file(&self) -> bool {
+        self.id_type() == H5I_FILE
+    }
+
+    /// Returns `true` if the object is a group.
+    pub fn is_group(&self) -> bool {
+        self.id_type() == H5I_GROUP
+    }
+
+    /// Returns `true` if the object is a dataset.
+    pub fn is_dataset(&self) -> bool {
+        self.id_type() == H5I_DATASET
+    }
+
+    /// Returns `true` if the object is a datatype.
+    pub fn is_datatype(&self) -> bool {
+        self.id_type() == H5I_DATATYPE
+    }
+
+    /// Returns `true` if the object is an attribute.
+    pub fn is_attr(&self) -> bool {
+        self.id_type() == H5I_ATTR
+    }
+}
+
+/// A weak reference to an HDF5 object which doesn't keep the object (or its file) open.
+pub struct WeakObject<T> {
+    handle: WeakHandle,
+    phantom: PhantomData<T>,
+}
+
+impl<T: ObjectClass> WeakObject<T> {
+    /// Creates a weak reference to an object without incrementing its reference count.
+    pub fn new(obj: &T) -> Self {
+        Self { handle: obj.handle().downgrade(), phantom: PhantomData }
+    }
+
+    /// Returns the object if it is still open, and `None` otherwise.
+    pub fn upgrade(&self) -> Option<T> {
+        h5lock!({
+            if T::is_valid_id_type(get_id_type(self.handle.id())) {
+                self.handle.upgrade().map(T::from_handle)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<T> Clone for WeakObject<T> {
+    fn clone(&self) -> Self {
+        Self { handle: self.handle.clone(), phantom: PhantomData }
+    }
+}
+
+impl<T> Debug for WeakObject<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("WeakObject").field(&self.handle.id()).finish()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
     use std::ops::Deref;
 
-    use hdf5_sys::{h5i::H5I_type_t, h5p::H5Pcreate};
+    use hdf5_sys::{h5i::H5I_type_t, h5o::H5Oopen, h5p::H5Pcreate};
 
     use crate::globals::H5P_FILE_ACCESS;
     use crate::handle::{is_valid_id, is_valid_user_id};
+    use crate::hl::object::{ObjectKind, WeakObject};
+    use crate::hl::plist::dataset_transfer::DatasetTransfer;
     use crate::internal_prelude::*;
 
     pub struct TestObject(Handle);
@@ -152,4 +265,87 @@ pub mod tests {
         assert_eq!(obj.id(), H5I_INVALID_HID);
         assert_eq!(obj2.id(), H5I_INVALID_HID);
     }
+
+    #[test]
+    pub fn test_object_kind() {
+        with_tmp_file(|file| {
+            let group = file.create_group("foo").unwrap();
+            let ds = group.new_dataset::<u32>().create("bar", 2).unwrap();
+            let attr = ds.new_attr::<u8>().create("baz", ()).unwrap();
+            let dt = ds.dtype().unwrap();
+
+            assert!(group.is_group());
+            assert!(!group.is_dataset() && !group.is_datatype() && !group.is_attr());
+            assert!(ds.is_dataset());
+            assert!(!ds.is_group() && !ds.is_datatype() && !ds.is_attr());
+            assert!(dt.is_datatype());
+            assert!(!dt.is_group() && !dt.is_dataset());
+            assert!(attr.is_attr());
+            assert!(!attr.is_dataset());
+            assert!(file.is_file() && !file.is_group());
+
+            assert_eq!(file.object_kind(), ObjectKind::File);
+            assert_eq!(group.object_kind(), ObjectKind::Group);
+            assert_eq!(ds.object_kind(), ObjectKind::Dataset);
+            assert_eq!(dt.object_kind(), ObjectKind::Datatype);
+            assert_eq!(attr.object_kind(), ObjectKind::Attribute);
+            assert_eq!(ds.space().unwrap().object_kind(), ObjectKind::Dataspace);
+            let plist = DatasetTransfer::try_new().unwrap();
+            assert_eq!(plist.object_kind(), ObjectKind::PropertyList);
+        })
+    }
+
+    #[test]
+    pub fn test_weak_object() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let ds = file.new_dataset::<u32>().create("foo", 1).unwrap();
+            let weak = WeakObject::new(&ds);
+            assert_eq!(ds.refcount(), 1);
+
+            let strong = weak.upgrade().unwrap();
+            assert_eq!(strong.name(), "/foo");
+            assert_eq!(ds.refcount(), 2);
+            drop(strong);
+            assert_eq!(ds.refcount(), 1);
+            drop(ds);
+            assert!(weak.upgrade().is_none());
+            assert!(weak.clone().upgrade().is_none());
+
+            let weak_file = WeakObject::new(&file);
+            assert!(weak_file.upgrade().unwrap().is_valid());
+            drop(file);
+            assert!(weak_file.upgrade().is_none());
+            File::open(&path).unwrap();
+        })
+    }
+
+    #[test]
+    pub fn test_downcast() {
+        with_tmp_file(|file| {
+            file.create_group("foo").unwrap();
+            file.new_dataset::<u8>().create("bar", 1).unwrap();
+            let open = |name| {
+                let name = to_cstring(name).unwrap();
+                Object::from_id(h5call!(H5Oopen(file.id(), name.as_ptr(), H5P_DEFAULT)).unwrap())
+                    .unwrap()
+            };
+
+            let group = open("foo").downcast::<Group>().unwrap();
+            assert_eq!(group.name(), "/foo");
+            assert_eq!(group.refcount(), 1);
+            let ds = open("bar").downcast::<Dataset>().unwrap();
+            assert_eq!(ds.name(), "/bar");
+            assert_eq!(ds.refcount(), 1);
+            assert_err!(open("foo").downcast::<Dataset>(), "Invalid dataset id");
+            assert_err!(open("bar").downcast::<Group>(), "Invalid group id");
+
+            let obj = open("foo");
+            let id = obj.id();
+            let group = obj.downcast::<Location>().unwrap();
+            assert_eq!(group.id(), id);
+            drop(group);
+            assert!(!is_valid_user_id(id));
+        })
+    }
 }
@@ -10,6 +10,7 @@ use hdf5_sys::h5p::{
 use crate::internal_prelude::*;
 
 pub mod dataset_access;
+pub mod dataset_transfer;
 pub mod file_access;
 pub mod file_create;
 
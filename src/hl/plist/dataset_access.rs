@@ -8,7 +8,10 @@ Not implemented:
 use std::fmt::{self, Debug};
 use std::ops::Deref;
 
-use hdf5_sys::h5p::{H5Pcreate, H5Pget_chunk_cache, H5Pset_chunk_cache};
+use hdf5_sys::h5p::{
+    H5Pcreate, H5Pget_chunk_cache, H5Pget_elink_fapl, H5Pget_elink_prefix, H5Pset_chunk_cache,
+    H5Pset_elink_fapl, H5Pset_elink_prefix,
+};
 #[cfg(all(hdf5_1_10_0, h5_have_parallel))]
 use hdf5_sys::h5p::{H5Pget_all_coll_metadata_ops, H5Pset_all_coll_metadata_ops};
 #[cfg(hdf5_1_8_17)]
@@ -23,6 +26,7 @@ use hdf5_sys::{
 };
 
 pub use super::file_access::ChunkCache;
+use super::file_access::FileAccess;
 use crate::globals::H5P_DATASET_ACCESS;
 use crate::internal_prelude::*;
 
@@ -56,6 +60,7 @@ impl Debug for DatasetAccess {
         let _e = silence_errors();
         let mut formatter = f.debug_struct("DatasetAccess");
         formatter.field("chunk_cache", &self.chunk_cache());
+        formatter.field("elink_prefix", &self.elink_prefix());
         #[cfg(hdf5_1_8_17)]
         formatter.field("efile_prefix", &self.efile_prefix());
         #[cfg(hdf5_1_10_0)]
@@ -129,6 +134,8 @@ impl From<VirtualView> for H5D_vds_view_t {
 #[derive(Clone, Debug, Default)]
 pub struct DatasetAccessBuilder {
     chunk_cache: Option<ChunkCache>,
+    elink_prefix: Option<String>,
+    elink_fapl: Option<FileAccess>,
     #[cfg(hdf5_1_8_17)]
     efile_prefix: Option<String>,
     #[cfg(hdf5_1_10_0)]
@@ -150,6 +157,8 @@ impl DatasetAccessBuilder {
         let mut builder = Self::default();
         let v = plist.get_chunk_cache()?;
         builder.chunk_cache(v.nslots, v.nbytes, v.w0);
+        builder.elink_prefix(&plist.get_elink_prefix()?);
+        builder.elink_fapl = plist.get_elink_fapl()?;
         #[cfg(hdf5_1_8_17)]
         {
             let v = plist.get_efile_prefix()?;
@@ -170,6 +179,19 @@ impl DatasetAccessBuilder {
         self
     }
 
+    /// Sets the prefix prepended to file names of external links when opening datasets
+    /// through them.
+    pub fn elink_prefix(&mut self, prefix: &str) -> &mut Self {
+        self.elink_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets the file access properties used to open target files of external links.
+    pub fn elink_fapl(&mut self, fapl: &FileAccess) -> &mut Self {
+        self.elink_fapl = Some(fapl.clone());
+        self
+    }
+
     #[cfg(hdf5_1_8_17)]
     pub fn efile_prefix(&mut self, prefix: &str) -> &mut Self {
         self.efile_prefix = Some(prefix.into());
@@ -198,6 +220,13 @@ impl DatasetAccessBuilder {
         if let Some(v) = self.chunk_cache {
             h5try!(H5Pset_chunk_cache(id, v.nslots as _, v.nbytes as _, v.w0 as _));
         }
+        if let Some(ref v) = self.elink_prefix {
+            let v = to_cstring(v.as_ref())?;
+            h5try!(H5Pset_elink_prefix(id, v.as_ptr()));
+        }
+        if let Some(ref v) = self.elink_fapl {
+            h5try!(H5Pset_elink_fapl(id, v.id()));
+        }
         #[cfg(hdf5_1_8_17)]
         {
             if let Some(ref v) = self.efile_prefix {
@@ -261,6 +290,32 @@ impl DatasetAccess {
         self.get_chunk_cache().unwrap_or_else(|_| ChunkCache::default())
     }
 
+    #[doc(hidden)]
+    pub fn get_elink_prefix(&self) -> Result<String> {
+        h5lock!(get_h5_str(|m, s| H5Pget_elink_prefix(self.id(), m, s)))
+    }
+
+    pub fn elink_prefix(&self) -> String {
+        self.get_elink_prefix().ok().unwrap_or_else(|| "".into())
+    }
+
+    #[doc(hidden)]
+    pub fn get_elink_fapl(&self) -> Result<Option<FileAccess>> {
+        h5lock!({
+            let id = h5try!(H5Pget_elink_fapl(self.id()));
+            if id == H5P_DEFAULT {
+                Ok(None)
+            } else {
+                FileAccess::from_id(id).map(Some)
+            }
+        })
+    }
+
+    /// Returns a copy of the file access property list used for external links, if set.
+    pub fn elink_fapl(&self) -> Option<FileAccess> {
+        self.get_elink_fapl().ok().and_then(|x| x)
+    }
+
     #[cfg(hdf5_1_8_17)]
     #[doc(hidden)]
     pub fn get_efile_prefix(&self) -> Result<String> {
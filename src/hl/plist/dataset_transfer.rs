@@ -0,0 +1,296 @@
+//! Dataset transfer properties.
+
+/*
+Not implemented:
+- H5P{set,get}_vlen_mem_manager (raw C extern callbacks)
+- handling conversion exceptions in H5Pset_type_conv_cb beyond aborting/continuing
+- user-allocated conversion/background buffers in H5Pset_buffer
+*/
+
+use std::fmt::{self, Debug};
+use std::mem;
+use std::ops::Deref;
+use std::panic;
+use std::ptr;
+
+#[cfg(h5_have_parallel)]
+use hdf5_sys::h5p::{H5FD_mpio_xfer_t, H5Pget_dxpl_mpio, H5Pset_dxpl_mpio};
+use hdf5_sys::h5p::{
+    H5Pcreate, H5Pget_buffer, H5Pget_preserve, H5Pget_type_conv_cb, H5Pset_buffer, H5Pset_preserve,
+    H5Pset_type_conv_cb,
+};
+use hdf5_sys::h5t::{H5T_conv_except_func_t, H5T_conv_except_t, H5T_conv_ret_t};
+
+use crate::globals::H5P_DATASET_XFER;
+use crate::internal_prelude::*;
+
+/// Dataset transfer properties.
+#[repr(transparent)]
+pub struct DatasetTransfer(Handle);
+
+impl ObjectClass for DatasetTransfer {
+    const NAME: &'static str = "dataset transfer property list";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_GENPROP_LST];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn validate(&self) -> Result<()> {
+        let class = self.class()?;
+        if class != PropertyListClass::DataTransfer {
+            fail!("expected dataset transfer property list, got {:?}", class);
+        }
+        Ok(())
+    }
+}
+
+impl Debug for DatasetTransfer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let _e = silence_errors();
+        let mut formatter = f.debug_struct("DatasetTransfer");
+        formatter.field("buffer_size", &self.buffer_size());
+        formatter.field("preserve", &self.preserve());
+        formatter.field("conversion_callback", &self.conversion_callback().is_some());
+        #[cfg(h5_have_parallel)]
+        formatter.field("collective", &self.collective());
+        formatter.finish()
+    }
+}
+
+impl Deref for DatasetTransfer {
+    type Target = PropertyList;
+
+    fn deref(&self) -> &PropertyList {
+        unsafe { self.transmute() }
+    }
+}
+
+impl PartialEq for DatasetTransfer {
+    fn eq(&self, other: &Self) -> bool {
+        <PropertyList as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for DatasetTransfer {}
+
+impl Clone for DatasetTransfer {
+    fn clone(&self) -> Self {
+        unsafe { self.deref().clone().cast() }
+    }
+}
+
+/// Exception raised while converting data between datatypes during transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionException {
+    /// Source value is greater than the maximum destination value.
+    RangeHigh,
+    /// Source value is less than the minimum destination value.
+    RangeLow,
+    /// Source value loses precision in the destination type.
+    Precision,
+    /// Source value is truncated (e.g. a float converted to an integer).
+    Truncate,
+    /// Source value is positive infinity.
+    PositiveInfinity,
+    /// Source value is negative infinity.
+    NegativeInfinity,
+    /// Source value is NaN.
+    NaN,
+}
+
+impl From<H5T_conv_except_t> for ConversionException {
+    fn from(except: H5T_conv_except_t) -> Self {
+        match except {
+            H5T_conv_except_t::H5T_CONV_EXCEPT_RANGE_HI => ConversionException::RangeHigh,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_RANGE_LOW => ConversionException::RangeLow,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_PRECISION => ConversionException::Precision,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_TRUNCATE => ConversionException::Truncate,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_PINF => ConversionException::PositiveInfinity,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_NINF => ConversionException::NegativeInfinity,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_NAN => ConversionException::NaN,
+        }
+    }
+}
+
+/// Action to take when a conversion exception is raised.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionAction {
+    /// Abort the data transfer, making it fail.
+    Abort,
+    /// Continue with the library's default handling (e.g. clamping or setting to infinity).
+    Continue,
+}
+
+/// Callback invoked on conversion exceptions, see `DatasetTransferBuilder::conversion_callback()`.
+pub type ConversionCallback = fn(ConversionException) -> ConversionAction;
+
+extern "C" fn conversion_exception_callback(
+    except_type: H5T_conv_except_t, _src_id: hid_t, _dst_id: hid_t, _src_buf: *mut c_void,
+    _dst_buf: *mut c_void, user_data: *mut c_void,
+) -> H5T_conv_ret_t {
+    let callback: ConversionCallback = unsafe { mem::transmute(user_data) };
+    // a panic must not unwind through the library, so it aborts the transfer instead
+    match panic::catch_unwind(|| callback(except_type.into())) {
+        Ok(ConversionAction::Continue) => H5T_conv_ret_t::H5T_CONV_UNHANDLED,
+        Ok(ConversionAction::Abort) | Err(_) => H5T_conv_ret_t::H5T_CONV_ABORT,
+    }
+}
+
+/// Builder used to create dataset transfer property list.
+#[derive(Clone, Debug, Default)]
+pub struct DatasetTransferBuilder {
+    buffer_size: Option<usize>,
+    preserve: Option<bool>,
+    conversion_callback: Option<ConversionCallback>,
+    #[cfg(h5_have_parallel)]
+    collective: Option<bool>,
+}
+
+impl DatasetTransferBuilder {
+    /// Creates a new dataset transfer property list builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new builder from an existing property list.
+    pub fn from_plist(plist: &DatasetTransfer) -> Result<Self> {
+        let mut builder = Self::default();
+        builder.buffer_size(plist.get_buffer_size()?);
+        builder.preserve(plist.get_preserve()?);
+        builder.conversion_callback = plist.get_conversion_callback()?;
+        #[cfg(h5_have_parallel)]
+        builder.collective(plist.get_collective()?);
+        Ok(builder)
+    }
+
+    /// Sets the size of the type conversion and background buffers (in bytes).
+    pub fn buffer_size(&mut self, size: usize) -> &mut Self {
+        self.buffer_size = Some(size);
+        self
+    }
+
+    /// Enables or disables preserving the background of compound datatypes
+    /// during partial I/O.
+    pub fn preserve(&mut self, preserve: bool) -> &mut Self {
+        self.preserve = Some(preserve);
+        self
+    }
+
+    /// Sets a callback invoked whenever a datatype conversion exception (such as an
+    /// overflow) is raised during the transfer, which decides whether to abort the
+    /// transfer or to continue with the default handling. A panic in the callback aborts
+    /// the transfer.
+    pub fn conversion_callback(&mut self, callback: ConversionCallback) -> &mut Self {
+        self.conversion_callback = Some(callback);
+        self
+    }
+
+    /// Selects collective (as opposed to independent) MPI-IO data transfer.
+    #[cfg(h5_have_parallel)]
+    pub fn collective(&mut self, is_collective: bool) -> &mut Self {
+        self.collective = Some(is_collective);
+        self
+    }
+
+    fn populate_plist(&self, id: hid_t) -> Result<()> {
+        if let Some(v) = self.buffer_size {
+            h5try!(H5Pset_buffer(id, v as _, ptr::null_mut(), ptr::null_mut()));
+        }
+        if let Some(v) = self.preserve {
+            h5try!(H5Pset_preserve(id, v as _));
+        }
+        if let Some(v) = self.conversion_callback {
+            let func: H5T_conv_except_func_t = Some(conversion_exception_callback);
+            h5try!(H5Pset_type_conv_cb(id, func, v as *mut c_void));
+        }
+        #[cfg(h5_have_parallel)]
+        {
+            if let Some(v) = self.collective {
+                let mode = if v {
+                    H5FD_mpio_xfer_t::H5FD_MPIO_COLLECTIVE
+                } else {
+                    H5FD_mpio_xfer_t::H5FD_MPIO_INDEPENDENT
+                };
+                h5try!(H5Pset_dxpl_mpio(id, mode));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(&self) -> Result<DatasetTransfer> {
+        h5lock!({
+            let plist = DatasetTransfer::try_new()?;
+            self.populate_plist(plist.id())?;
+            Ok(plist)
+        })
+    }
+}
+
+/// Dataset transfer property list.
+impl DatasetTransfer {
+    pub fn try_new() -> Result<Self> {
+        Self::from_id(h5try!(H5Pcreate(*H5P_DATASET_XFER)))
+    }
+
+    pub fn copy(&self) -> Self {
+        unsafe { self.deref().copy().cast() }
+    }
+
+    pub fn build() -> DatasetTransferBuilder {
+        DatasetTransferBuilder::new()
+    }
+
+    #[doc(hidden)]
+    pub fn get_buffer_size(&self) -> Result<usize> {
+        h5call!(H5Pget_buffer(self.id(), ptr::null_mut(), ptr::null_mut())).map(|x| x as _)
+    }
+
+    pub fn buffer_size(&self) -> usize {
+        self.get_buffer_size().unwrap_or(1024 * 1024)
+    }
+
+    #[doc(hidden)]
+    pub fn get_preserve(&self) -> Result<bool> {
+        h5call!(H5Pget_preserve(self.id())).map(|x| x > 0)
+    }
+
+    pub fn preserve(&self) -> bool {
+        self.get_preserve().unwrap_or(false)
+    }
+
+    #[doc(hidden)]
+    pub fn get_conversion_callback(&self) -> Result<Option<ConversionCallback>> {
+        let mut func: H5T_conv_except_func_t = None;
+        let mut user_data: *mut c_void = ptr::null_mut();
+        h5try!(H5Pget_type_conv_cb(self.id(), &mut func, &mut user_data));
+        let ours: H5T_conv_except_func_t = Some(conversion_exception_callback);
+        let same = func.map(|f| f as usize) == ours.map(|f| f as usize);
+        if same && !user_data.is_null() {
+            Ok(Some(unsafe { mem::transmute::<*mut c_void, ConversionCallback>(user_data) }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn conversion_callback(&self) -> Option<ConversionCallback> {
+        self.get_conversion_callback().unwrap_or(None)
+    }
+
+    #[cfg(h5_have_parallel)]
+    #[doc(hidden)]
+    pub fn get_collective(&self) -> Result<bool> {
+        let mut mode = H5FD_mpio_xfer_t::H5FD_MPIO_INDEPENDENT;
+        h5call!(H5Pget_dxpl_mpio(self.id(), &mut mode))
+            .map(|_| mode == H5FD_mpio_xfer_t::H5FD_MPIO_COLLECTIVE)
+    }
+
+    #[cfg(h5_have_parallel)]
+    pub fn collective(&self) -> bool {
+        self.get_collective().unwrap_or(false)
+    }
+}
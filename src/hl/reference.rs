@@ -0,0 +1,137 @@
+use hdf5_sys::h5r::{hdset_reg_ref_t, H5R_type_t::H5R_DATASET_REGION, H5Rcreate, H5Rget_region};
+use hdf5_types::TypeDescriptor;
+
+use crate::internal_prelude::*;
+
+/// Reference to a region (a selection of elements) within a dataset.
+///
+/// Region references are plain values which remain valid as long as the referenced
+/// dataset exists; they can be resolved through any object in the same file.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegionReference(hdset_reg_ref_t);
+
+unsafe impl H5Type for RegionReference {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::RegionReference
+    }
+}
+
+impl RegionReference {
+    /// Creates a reference to the elements selected in `selection` within a dataset.
+    pub fn new(dataset: &Dataset, selection: &Dataspace) -> Result<Self> {
+        let mut reference = hdset_reg_ref_t::default();
+        let name = to_cstring(".")?;
+        h5try!(H5Rcreate(
+            reference.as_mut_ptr() as *mut _,
+            dataset.id(),
+            name.as_ptr(),
+            H5R_DATASET_REGION,
+            selection.id()
+        ));
+        Ok(Self(reference))
+    }
+
+    fn as_ptr(&self) -> *const c_void {
+        self.0.as_ptr() as *const _
+    }
+
+    /// Opens the referenced dataset; `loc` can be any object in the same file.
+    pub fn dataset(&self, loc: &Location) -> Result<Dataset> {
+        #[cfg(hdf5_1_10_0)]
+        let id = h5try!(hdf5_sys::h5r::H5Rdereference2(
+            loc.id(),
+            H5P_DEFAULT,
+            H5R_DATASET_REGION,
+            self.as_ptr()
+        ));
+        #[cfg(not(hdf5_1_10_0))]
+        let id = h5try!(hdf5_sys::h5r::H5Rdereference(loc.id(), H5R_DATASET_REGION, self.as_ptr()));
+        Dataset::from_id(id)
+    }
+
+    /// Returns a copy of the dataspace of the referenced dataset with the referenced
+    /// region selected; `loc` can be any object in the same file.
+    pub fn region(&self, loc: &Location) -> Result<Dataspace> {
+        Dataspace::from_id(h5try!(H5Rget_region(loc.id(), H5R_DATASET_REGION, self.as_ptr())))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ndarray::s;
+
+    use hdf5_sys::h5a::{H5Acreate2, H5Aread, H5Awrite};
+
+    use crate::globals::H5T_STD_REF_DSETREG;
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_region_reference_size() {
+        use hdf5_sys::h5r::hdset_reg_ref_t;
+        use hdf5_types::TypeDescriptor;
+
+        assert_eq!(TypeDescriptor::RegionReference.size(), std::mem::size_of::<hdset_reg_ref_t>());
+    }
+
+    #[test]
+    pub fn test_region_reference() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().create("foo", (5, 5)).unwrap();
+            let space = ds.space().unwrap();
+            space.select_slice(s![1..4, 2..5]).unwrap();
+            let reference = ds.region_reference(&space).unwrap();
+
+            let region = reference.region(&file).unwrap();
+            assert_eq!(region.selection_size(), 9);
+            assert_eq!(region.dims(), vec![5, 5]);
+            assert_eq!(reference.dataset(&file).unwrap().name(), "/foo");
+
+            let attr = h5call!(H5Acreate2(
+                file.id(),
+                to_cstring("ref").unwrap().as_ptr(),
+                *H5T_STD_REF_DSETREG,
+                Dataspace::try_new((), false).unwrap().id(),
+                H5P_DEFAULT,
+                H5P_DEFAULT
+            ))
+            .unwrap();
+            let attr = Attribute::from_id(attr).unwrap();
+            h5call!(H5Awrite(attr.id(), *H5T_STD_REF_DSETREG, &reference as *const _ as *const _))
+                .unwrap();
+            let mut stored: RegionReference = unsafe { std::mem::zeroed() };
+            let attr = file.attr("ref").unwrap();
+            h5call!(H5Aread(attr.id(), *H5T_STD_REF_DSETREG, &mut stored as *mut _ as *mut _))
+                .unwrap();
+            assert_eq!(stored, reference);
+            assert_eq!(stored.region(&attr).unwrap().selection_size(), 9);
+        })
+    }
+
+    #[test]
+    pub fn test_region_reference_dataset() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<f32>().create("a/data", (4, 6)).unwrap();
+            let (s1, s2) = (ds.space().unwrap(), ds.space().unwrap());
+            s1.select_slice(s![0..2, ..]).unwrap();
+            s2.select_slice(s![1..4, 3..4]).unwrap();
+            let refs = vec![ds.region_reference(&s1).unwrap(), ds.region_reference(&s2).unwrap()];
+
+            let refs_ds = file.new_dataset::<RegionReference>().create("refs", 2).unwrap();
+            refs_ds.write_raw(&refs).unwrap();
+            assert_eq!(
+                refs_ds.dtype().unwrap().to_descriptor().unwrap(),
+                RegionReference::type_descriptor()
+            );
+
+            let read = file.dataset("refs").unwrap().read_raw::<RegionReference>().unwrap();
+            assert_eq!(read, refs);
+            let sizes: Vec<_> =
+                read.iter().map(|r| r.region(&refs_ds).unwrap().selection_size()).collect();
+            assert_eq!(sizes, vec![12, 3]);
+            for r in &read {
+                assert_eq!(r.dataset(&file).unwrap().name(), "/a/data");
+            }
+        })
+    }
+}
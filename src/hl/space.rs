@@ -5,9 +5,17 @@ use std::ptr;
 
 use ndarray::SliceOrIndex;
 
+use hdf5_sys::h5::hssize_t;
 use hdf5_sys::h5s::{
-    H5Scopy, H5Screate_simple, H5Sget_simple_extent_dims, H5Sget_simple_extent_ndims,
-    H5Sselect_hyperslab, H5S_SELECT_SET,
+    H5S_class_t, H5Scopy, H5Screate_simple, H5Sdecode, H5Sencode, H5Sextent_copy, H5Sextent_equal,
+    H5Sget_select_npoints, H5Sget_simple_extent_dims, H5Sget_simple_extent_ndims,
+    H5Sget_simple_extent_type, H5Sis_simple, H5Soffset_simple, H5Sselect_hyperslab,
+    H5Sselect_valid, H5S_SELECT_SET,
+};
+
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::h5s::{
+    H5S_sel_type, H5Sget_regular_hyperslab, H5Sget_select_type, H5Sis_regular_hyperslab,
 };
 
 use crate::internal_prelude::*;
@@ -30,6 +38,11 @@ impl ObjectClass for Dataspace {
     }
 
     fn short_repr(&self) -> Option<String> {
+        match h5lock!(H5Sget_simple_extent_type(self.id())) {
+            H5S_class_t::H5S_SCALAR => return Some("scalar".into()),
+            H5S_class_t::H5S_NULL => return Some("null".into()),
+            _ => (),
+        }
         if self.ndim() == 1 {
             Some(format!("({},)", self.dims()[0]))
         } else {
@@ -93,6 +106,43 @@ impl Dataspace {
         Ok(shape_vec)
     }
 
+    /// Selects a regular hyperslab given its start, stride, count and block size along
+    /// every dimension (the same representation as returned by `get_regular_hyperslab()`).
+    ///
+    /// Strides, counts and block sizes must all be positive.
+    pub fn select_hyperslab(
+        &self, start: &[Ix], stride: &[Ix], count: &[Ix], block: &[Ix],
+    ) -> Result<()> {
+        let ndim = self.ndim();
+        for (name, v) in &[("start", start), ("stride", stride), ("count", count), ("block", block)]
+        {
+            ensure!(
+                v.len() == ndim,
+                "hyperslab {} ndim mismatch: expected {}, got {}",
+                name,
+                ndim,
+                v.len()
+            );
+        }
+        for (name, v) in &[("stride", stride), ("count", count), ("block", block)] {
+            if let Some(axis) = v.iter().position(|&x| x == 0) {
+                fail!("{} must be positive, got 0 on axis {}", name, axis);
+            }
+        }
+        let to_vec = |v: &[Ix]| v.iter().map(|&x| x as hsize_t).collect::<Vec<_>>();
+        let (start, stride, count, block) =
+            (to_vec(start), to_vec(stride), to_vec(count), to_vec(block));
+        h5call!(H5Sselect_hyperslab(
+            self.id(),
+            H5S_SELECT_SET,
+            start.as_ptr(),
+            stride.as_ptr(),
+            count.as_ptr(),
+            block.as_ptr()
+        ))
+        .and(Ok(()))
+    }
+
     fn get_start_stride_count(v: &SliceOrIndex, len: Ix) -> Result<(u64, u64, u64)> {
         match v {
             SliceOrIndex::Slice { start, end, step } => {
@@ -123,6 +173,22 @@ impl Dataspace {
         Self::from_id(h5try!(H5Screate_simple(rank as _, dims.as_ptr(), max_dims.as_ptr())))
     }
 
+    /// Creates a simple dataspace with the maximum extent set separately for each dimension,
+    /// where `None` stands for an unlimited dimension.
+    pub fn new_with_maxdims<D: Dimension>(d: D, maxdims: &[Option<Ix>]) -> Result<Self> {
+        let rank = d.ndim();
+        ensure!(
+            maxdims.len() == rank,
+            "maxdims ndim mismatch: expected {}, got {}",
+            rank,
+            maxdims.len()
+        );
+        let dims: Vec<hsize_t> = d.dims().iter().map(|&x| x as _).collect();
+        let max_dims: Vec<hsize_t> =
+            maxdims.iter().map(|x| x.map_or(H5S_UNLIMITED, |x| x as _)).collect();
+        Self::from_id(h5try!(H5Screate_simple(rank as _, dims.as_ptr(), max_dims.as_ptr())))
+    }
+
     pub fn maxdims(&self) -> Vec<Ix> {
         let ndim = self.ndim();
         if ndim > 0 {
@@ -142,6 +208,96 @@ impl Dataspace {
     pub fn resizable(&self) -> bool {
         self.maxdims().iter().any(|&x| x == H5S_UNLIMITED as _)
     }
+
+    /// Returns the number of elements in the current selection of the dataspace.
+    pub fn selection_size(&self) -> usize {
+        h5call!(H5Sget_select_npoints(self.id())).unwrap_or(0) as _
+    }
+
+    /// Shifts the current selection by `offset` (one signed element per dimension) for
+    /// subsequent I/O, without changing the selection itself.
+    pub fn set_offset(&self, offset: &[isize]) -> Result<()> {
+        let ndim = self.ndim();
+        ensure!(
+            offset.len() == ndim,
+            "offset dimension mismatch: dataspace has {} dims, offset has {} dims",
+            ndim,
+            offset.len()
+        );
+        let offset: Vec<hssize_t> = offset.iter().map(|&x| x as _).collect();
+        h5call!(H5Soffset_simple(self.id(), offset.as_ptr())).and(Ok(()))
+    }
+
+    /// Returns true if the current selection lies entirely within the extent of the dataspace.
+    pub fn selection_valid(&self) -> bool {
+        h5call!(H5Sselect_valid(self.id())).unwrap_or(0) > 0
+    }
+
+    /// Serializes the dataspace, including its extent and current selection, into bytes.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        h5lock!({
+            let mut len: size_t = 0;
+            h5try!(H5Sencode(self.id(), ptr::null_mut(), &mut len));
+            let mut buf = vec![0_u8; len];
+            h5try!(H5Sencode(self.id(), buf.as_mut_ptr() as *mut _, &mut len));
+            Ok(buf)
+        })
+    }
+
+    /// Reconstructs a dataspace from the bytes produced by `encode()`.
+    pub fn decode(buf: &[u8]) -> Result<Self> {
+        ensure!(!buf.is_empty(), "cannot decode dataspace from an empty buffer");
+        Self::from_id(h5try!(H5Sdecode(buf.as_ptr() as *const _)))
+    }
+
+    /// Returns true if the current selection is a single regular hyperslab.
+    #[cfg(hdf5_1_10_0)]
+    pub fn is_regular_hyperslab(&self) -> bool {
+        h5lock!({
+            H5Sget_select_type(self.id()) == H5S_sel_type::H5S_SEL_HYPERSLABS
+                && h5call!(H5Sis_regular_hyperslab(self.id())).unwrap_or(0) > 0
+        })
+    }
+
+    /// Returns the `(start, stride, count, block)` parameters of the current selection if
+    /// it is a single regular hyperslab, and `None` otherwise.
+    #[cfg(hdf5_1_10_0)]
+    pub fn get_regular_hyperslab(&self) -> Option<(Vec<Ix>, Vec<Ix>, Vec<Ix>, Vec<Ix>)> {
+        h5lock!({
+            if !self.is_regular_hyperslab() {
+                return None;
+            }
+            let ndim = self.ndim();
+            let (mut start, mut stride) = (vec![0; ndim], vec![0; ndim]);
+            let (mut count, mut block) = (vec![0; ndim], vec![0; ndim]);
+            h5call!(H5Sget_regular_hyperslab(
+                self.id(),
+                start.as_mut_ptr(),
+                stride.as_mut_ptr(),
+                count.as_mut_ptr(),
+                block.as_mut_ptr()
+            ))
+            .ok()?;
+            let conv = |v: Vec<hsize_t>| v.into_iter().map(|x| x as _).collect();
+            Some((conv(start), conv(stride), conv(count), conv(block)))
+        })
+    }
+
+    /// Returns true if the dataspace is simple (neither scalar nor null).
+    pub fn is_simple(&self) -> bool {
+        h5call!(H5Sis_simple(self.id())).unwrap_or(0) > 0
+    }
+
+    /// Replaces the extent of the dataspace with the extent of `other` (including its
+    /// class and maximum dimensions); the current selection is reset to the whole extent.
+    pub fn copy_extent_from(&self, other: &Self) -> Result<()> {
+        h5call!(H5Sextent_copy(self.id(), other.id())).and(Ok(()))
+    }
+
+    /// Returns true if the dataspace has the same extent as `other` (selections are ignored).
+    pub fn extent_equal(&self, other: &Self) -> bool {
+        h5call!(H5Sextent_equal(self.id(), other.id())).unwrap_or(0) > 0
+    }
 }
 
 impl Dimension for Dataspace {
@@ -168,6 +324,10 @@ impl Dimension for Dataspace {
 
 #[cfg(test)]
 pub mod tests {
+    use hdf5_sys::h5s::{H5Screate, H5S_NULL};
+
+    use ndarray::s;
+
     use crate::internal_prelude::*;
 
     #[test]
@@ -188,9 +348,32 @@ pub mod tests {
         assert_eq!(f(&vec![4, 5]), (2, vec![4, 5], 20));
     }
 
+    #[test]
+    pub fn test_ndarray_dimension() {
+        use ndarray::{Ix0, Ix2, Ix3, IxDyn};
+
+        fn f<D: Dimension>(d: D) -> (usize, Vec<Ix>, Ix) {
+            (d.ndim(), d.dims(), d.size())
+        }
+
+        assert_eq!(f(Ix0()), (0, vec![], 1));
+        assert_eq!(f(Ix2(3, 4)), (2, vec![3, 4], 12));
+        assert_eq!(f(Ix3(2, 2, 2)), (3, vec![2, 2, 2], 8));
+        assert_eq!(f(IxDyn(&[1, 2, 3])), (3, vec![1, 2, 3], 6));
+
+        let space = Dataspace::try_new(Ix3(2, 3, 4), false).unwrap();
+        assert_eq!(space.dims(), vec![2, 3, 4]);
+        assert_eq!(space.size(), 24);
+    }
+
     #[test]
     pub fn test_debug() {
-        assert_eq!(format!("{:?}", Dataspace::try_new((), true).unwrap()), "<HDF5 dataspace: ()>");
+        assert_eq!(
+            format!("{:?}", Dataspace::try_new((), true).unwrap()),
+            "<HDF5 dataspace: scalar>"
+        );
+        let null = Dataspace::from_id(h5call!(H5Screate(H5S_NULL)).unwrap()).unwrap();
+        assert_eq!(format!("{:?}", null), "<HDF5 dataspace: null>");
         assert_eq!(format!("{:?}", Dataspace::try_new(3, true).unwrap()), "<HDF5 dataspace: (3,)>");
         assert_eq!(
             format!("{:?}", Dataspace::try_new((1, 2), true).unwrap()),
@@ -226,4 +409,171 @@ pub mod tests {
         );
         assert_eq!(Dataspace::try_new((5, 6), true).unwrap().resizable(), true);
     }
+
+    #[test]
+    pub fn test_is_simple() {
+        assert!(Dataspace::try_new((2, 3), false).unwrap().is_simple());
+        assert!(Dataspace::try_new(0, false).unwrap().is_simple());
+        assert!(!Dataspace::try_new((), false).unwrap().is_simple());
+        let null = Dataspace::from_id(h5call!(H5Screate(H5S_NULL)).unwrap()).unwrap();
+        assert!(!null.is_simple());
+        assert_eq!(null.ndim(), 0);
+    }
+
+    #[test]
+    pub fn test_copy_extent() {
+        let src = Dataspace::try_new((3, 4), false).unwrap();
+        src.select_slice(s![1.., 1..]).unwrap();
+        let space = Dataspace::try_new((), false).unwrap();
+        assert!(!space.extent_equal(&src));
+        space.copy_extent_from(&src).unwrap();
+        assert_eq!(space.dims(), vec![3, 4]);
+        assert!(space.extent_equal(&src));
+        assert!(src.extent_equal(&space));
+        assert_eq!(space.selection_size(), 12);
+        assert_eq!(src.selection_size(), 6);
+
+        space.select_slice(s![0, ..]).unwrap();
+        space.copy_extent_from(&Dataspace::try_new(5, true).unwrap()).unwrap();
+        assert_eq!(space.dims(), vec![5]);
+        assert!(space.resizable());
+        assert_eq!(space.selection_size(), 5);
+        assert!(!space.extent_equal(&src));
+        assert!(!space.extent_equal(&Dataspace::try_new(5, false).unwrap()));
+    }
+
+    #[test]
+    pub fn test_selection_valid() {
+        let space = Dataspace::try_new((3, 4), false).unwrap();
+        assert!(space.selection_valid());
+        space.select_slice(s![1..3, 2]).unwrap();
+        assert!(space.selection_valid());
+        space.select_slice(s![3, ..]).unwrap();
+        assert!(!space.selection_valid());
+        space.select_slice(s![.., 1..4;2]).unwrap();
+        assert!(space.selection_valid());
+
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u8>().create("foo", (3, 4)).unwrap();
+            assert_eq!(ds.read_slice::<u8, _, _>(&s![2, ..]).unwrap().len(), 4);
+            assert_err!(
+                ds.read_slice::<u8, _, _>(&s![3, ..]),
+                "selection is out of dataspace bounds"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_select_slice() {
+        let space = Dataspace::try_new((10, 10), false).unwrap();
+        assert_eq!(space.select_slice(s![2..5, 0..3]).unwrap(), vec![3, 3]);
+        assert_eq!(space.selection_size(), 9);
+        assert_eq!(space.select_slice(s![.., 0..7;2]).unwrap(), vec![10, 4]);
+        assert_eq!(space.selection_size(), 40);
+        assert_eq!(space.select_slice(s![4, 1..;3]).unwrap(), vec![1, 3]);
+        assert_eq!(space.selection_size(), 3);
+        assert_err!(space.select_slice(s![0..11, ..]), "slice extends beyond dataspace bounds");
+    }
+
+    #[test]
+    pub fn test_select_hyperslab() {
+        let space = Dataspace::try_new((10, 20), false).unwrap();
+        space.select_hyperslab(&[1, 2], &[4, 5], &[2, 3], &[2, 2]).unwrap();
+        assert_eq!(space.selection_size(), 24);
+        assert!(space.selection_valid());
+
+        assert_err!(
+            space.select_hyperslab(&[0, 0], &[1, 0], &[1, 1], &[1, 1]),
+            "stride must be positive, got 0 on axis 1"
+        );
+        assert_err!(
+            space.select_hyperslab(&[0, 0], &[1, 1], &[0, 1], &[1, 1]),
+            "count must be positive, got 0 on axis 0"
+        );
+        assert_err!(
+            space.select_hyperslab(&[0, 0], &[1, 1], &[1, 1], &[1, 0]),
+            "block must be positive, got 0 on axis 1"
+        );
+        assert_err!(
+            space.select_hyperslab(&[0], &[1, 1], &[1, 1], &[1, 1]),
+            "hyperslab start ndim mismatch: expected 2, got 1"
+        );
+        assert_err!(space.select_slice(s![..;-1, ..]), "step must be >= 1 (got -1)");
+        assert_eq!(space.selection_size(), 24);
+    }
+
+    #[test]
+    pub fn test_set_offset() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().create("foo", (4, 4)).unwrap();
+            ds.write_raw(&(0..16).collect::<Vec<_>>()).unwrap();
+            let space = ds.space().unwrap();
+            space.select_slice(s![0..2, 0..2]).unwrap();
+            assert_eq!(ds.read_selection::<u32>(&space).unwrap(), vec![0, 1, 4, 5]);
+
+            space.set_offset(&[1, 2]).unwrap();
+            assert_eq!(space.selection_size(), 4);
+            assert_eq!(ds.read_selection::<u32>(&space).unwrap(), vec![6, 7, 10, 11]);
+            let space = ds.space().unwrap();
+            space.select_slice(s![2..4, 1..3]).unwrap();
+            space.set_offset(&[-1, 1]).unwrap();
+            assert_eq!(ds.read_selection::<u32>(&space).unwrap(), vec![6, 7, 10, 11]);
+
+            space.set_offset(&[3, 0]).unwrap();
+            assert!(!space.selection_valid());
+            assert_err!(ds.read_selection::<u32>(&space), "selection is out of dataspace bounds");
+            assert_err!(space.set_offset(&[1]), "offset dimension mismatch");
+        })
+    }
+
+    #[test]
+    pub fn test_encode_decode() {
+        let space = Dataspace::try_new((10, 20), true).unwrap();
+        space.select_slice(s![2..8;2, 5..15]).unwrap();
+        let buf = space.encode().unwrap();
+        assert!(!buf.is_empty());
+
+        let decoded = Dataspace::decode(&buf).unwrap();
+        assert_eq!(decoded.dims(), vec![10, 20]);
+        assert!(decoded.resizable());
+        assert_eq!(decoded.selection_size(), 30);
+        assert_eq!(decoded.selection_size(), space.selection_size());
+        assert_err!(Dataspace::decode(&[]), "cannot decode dataspace from an empty buffer");
+    }
+
+    #[test]
+    pub fn test_new_with_maxdims() {
+        let space = Dataspace::new_with_maxdims((2, 5), &[None, Some(5)]).unwrap();
+        assert_eq!(space.dims(), vec![2, 5]);
+        assert_eq!(space.maxdims(), vec![H5S_UNLIMITED as Ix, 5]);
+        assert!(space.resizable());
+
+        let space = Dataspace::new_with_maxdims(3, &[Some(10)]).unwrap();
+        assert_eq!(space.maxdims(), vec![10]);
+        assert!(!space.resizable());
+
+        assert_err!(Dataspace::new_with_maxdims((2, 5), &[None]), "maxdims ndim mismatch");
+        assert_err!(Dataspace::new_with_maxdims(3, &[Some(2)]), "maxdims is smaller than dims");
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_0)]
+    pub fn test_regular_hyperslab() {
+        let space = Dataspace::try_new((10, 20), false).unwrap();
+        assert!(!space.is_regular_hyperslab());
+        assert!(space.get_regular_hyperslab().is_none());
+
+        space.select_slice(s![1..9;3, 4..10]).unwrap();
+        assert!(space.is_regular_hyperslab());
+        assert_eq!(
+            space.get_regular_hyperslab().unwrap(),
+            (vec![1, 4], vec![3, 1], vec![3, 6], vec![1, 1])
+        );
+
+        space.select_slice(s![2, ..]).unwrap();
+        assert_eq!(
+            space.get_regular_hyperslab().unwrap(),
+            (vec![2, 0], vec![1, 1], vec![1, 20], vec![1, 1])
+        );
+    }
 }
@@ -20,11 +20,14 @@ mod export {
     pub use crate::{
         class::from_id,
         dim::{Dimension, Ix},
-        error::{silence_errors, Error, Result},
-        filters::Filters,
+        error::{silence_errors, Error, Result, ThreadErrorGuard},
+        filters::{filter_available, filter_info, FilterFlags, Filters},
         hl::{
-            Container, Conversion, Dataset, DatasetBuilder, Dataspace, Datatype, File, FileBuilder,
-            Group, Location, Object, PropertyList, Reader, Writer,
+            Attribute, AttributeBuilder, BitPadding, ByteOrder, CharSet, CompoundMemberDescription,
+            Container, Conversion, Dataset, DatasetBuilder, Dataspace, Datatype, DatatypeClass,
+            DatatypeDescription, File, FileBuilder, FloatFields, FloatNormalization, Group,
+            GroupBuilder, IndexHeapSize, Location, NamedObject, NativeInfo, Object, ObjectKind,
+            PropertyList, Reader, RegionReference, StringPadding, Token, WeakObject, Writer,
         },
     };
 
@@ -36,18 +39,29 @@ mod export {
     }
 
     pub mod dataset {
-        pub use crate::hl::dataset::{Chunk, Dataset, DatasetBuilder};
+        pub use crate::hl::container::{RowChunks, VlenBuffer};
+        #[cfg(hdf5_1_10_5)]
+        pub use crate::hl::dataset::ChunkInfo;
+        pub use crate::hl::dataset::{AllocTime, Chunk, Dataset, DatasetBuilder};
         pub use crate::hl::plist::dataset_access::*;
+        pub use crate::hl::plist::dataset_transfer::*;
+    }
+
+    pub mod group {
+        pub use crate::hl::group::{Group, GroupBuilder, IndexType, LinkTarget};
     }
 
     pub mod file {
-        pub use crate::hl::file::{File, FileBuilder, OpenMode};
+        #[cfg(hdf5_1_10_0)]
+        pub use crate::hl::file::FileInfo;
+        pub use crate::hl::file::{File, FileBuilder, ObjectTypeMask, OpenMode};
         pub use crate::hl::plist::file_access::*;
         pub use crate::hl::plist::file_create::*;
     }
 
     pub mod plist {
         pub use crate::hl::plist::dataset_access::DatasetAccess;
+        pub use crate::hl::plist::dataset_transfer::DatasetTransfer;
         pub use crate::hl::plist::file_access::FileAccess;
         pub use crate::hl::plist::file_create::FileCreate;
         pub use crate::hl::plist::{PropertyList, PropertyListClass};
@@ -55,6 +69,9 @@ mod export {
         pub mod dataset_access {
             pub use crate::hl::plist::dataset_access::*;
         }
+        pub mod dataset_transfer {
+            pub use crate::hl::plist::dataset_transfer::*;
+        }
         pub mod file_access {
             pub use crate::hl::plist::file_access::*;
         }
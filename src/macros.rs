@@ -104,6 +104,20 @@ macro_rules! h5call {
     };
 }
 
+/// Evaluates to the byte offset of a field within a struct, for use with
+/// `Datatype::insert_member()` when building compound datatypes by hand.
+#[macro_export]
+macro_rules! h5_offset_of {
+    ($ty:ty, $field:ident) => {{
+        let value = ::std::mem::MaybeUninit::<$ty>::uninit();
+        let base = value.as_ptr();
+        // Never create a reference into the uninitialized value, only a raw pointer.
+        #[allow(unused_unsafe)]
+        let field = unsafe { ::std::ptr::addr_of!((*base).$field) };
+        (field as *const u8 as usize) - (base as *const u8 as usize)
+    }};
+}
+
 /// `h5try!(..)` is equivalent to try!(h5call!(..)).
 macro_rules! h5try {
     ($expr:expr) => {
@@ -1,7 +1,9 @@
 #[allow(unused_macros)]
 macro_rules! assert_err {
-    ($expr:expr, $err:expr) => {
-        match unsafe { $expr } {
+    ($expr:expr, $err:expr) => {{
+        #[allow(unused_unsafe)]
+        let result = unsafe { $expr };
+        match result {
             Ok(_) => {
                 panic!("assertion failed: not an error in `{}`", stringify!($expr));
             }
@@ -17,5 +19,5 @@ macro_rules! assert_err {
                 }
             }
         }
-    };
+    }};
 }
@@ -5,6 +5,7 @@ use rand::prelude::{Rng, SeedableRng, SmallRng};
 
 use hdf5_types::TypeDescriptor;
 
+#[macro_use]
 mod common;
 
 use self::common::gen::{gen_arr, gen_slice, Enum, FixedStruct, Gen, TupleStruct, VarLenStruct};
@@ -257,3 +258,50 @@ fn test_read_write_tuples() -> hdf5::Result<()> {
     test_read_write::<(i8, u64, f32)>()?;
     Ok(())
 }
+
+#[derive(hdf5::H5Type, Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Record {
+    x: i32,
+    y: f64,
+    z: u8,
+}
+
+#[test]
+fn test_write_field() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let data = [Record { x: 1, y: 1.5, z: 10 }, Record { x: 2, y: 2.5, z: 20 }];
+    let ds = file.new_dataset::<Record>().create("foo", 2)?;
+    ds.write_raw(&data)?;
+
+    ds.write_field("y", &[-1.0f64, -2.0])?;
+    assert_eq!(
+        ds.read_raw::<Record>()?,
+        vec![Record { x: 1, y: -1.0, z: 10 }, Record { x: 2, y: -2.0, z: 20 }]
+    );
+    ds.write_field("z", &[7u16, 8])?;
+    assert_eq!(
+        ds.read_raw::<Record>()?,
+        vec![Record { x: 1, y: -1.0, z: 7 }, Record { x: 2, y: -2.0, z: 8 }]
+    );
+
+    assert_err!(ds.write_field("w", &[0i32, 0]), "compound field not found: w");
+    assert_err!(ds.write_field("x", &[0i32]), "length mismatch when writing field");
+    let ds = file.new_dataset::<i32>().create("bar", 2)?;
+    assert_err!(ds.write_field("x", &[0i32, 0]), "dataset is not of compound type");
+    Ok(())
+}
+
+#[test]
+fn test_read_field() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let data = [Record { x: 1, y: 1.5, z: 10 }, Record { x: 2, y: 2.5, z: 20 }];
+    let ds = file.new_dataset::<Record>().create("foo", 2)?;
+    ds.write_raw(&data)?;
+
+    assert_eq!(ds.read_field::<f64>("y")?, vec![1.5, 2.5]);
+    assert_eq!(ds.read_field::<i32>("x")?, vec![1, 2]);
+    assert_eq!(ds.read_field::<f32>("z")?, vec![10.0, 20.0]);
+    assert_err!(ds.read_field::<f64>("w"), "compound field not found: w");
+    Ok(())
+}
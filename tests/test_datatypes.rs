@@ -1,8 +1,12 @@
 #[macro_use]
 mod common;
 
+use std::mem;
+
+use self::common::util::new_in_memory_file;
+
 use hdf5::types::{TypeDescriptor as TD, *};
-use hdf5::{from_id, Datatype, H5Type};
+use hdf5::{from_id, CharSet, Datatype, DatatypeClass, H5Type, StringPadding};
 
 use hdf5_sys::h5i::H5I_INVALID_HID;
 
@@ -97,7 +101,524 @@ pub fn test_eq() {
     assert_ne!(Datatype::from_type::<u16>().unwrap(), Datatype::from_type::<u32>().unwrap());
 }
 
+#[test]
+pub fn test_equal_ignoring_order() {
+    use hdf5_sys::h5t::{H5Tset_order, H5T_ORDER_BE, H5T_ORDER_LE};
+
+    let be = Datatype::from_type::<i32>().unwrap();
+    let le = Datatype::from_type::<i32>().unwrap();
+    unsafe {
+        assert!(H5Tset_order(be.id(), H5T_ORDER_BE) >= 0);
+        assert!(H5Tset_order(le.id(), H5T_ORDER_LE) >= 0);
+    }
+    assert_ne!(be, le);
+    assert!(be.equal_ignoring_order(&le));
+    assert!(le.equal_ignoring_order(&be));
+    assert!(be.equal_ignoring_order(&be));
+    assert!(!be.equal_ignoring_order(&Datatype::from_type::<u32>().unwrap()));
+    assert!(!be.equal_ignoring_order(&Datatype::from_type::<i64>().unwrap()));
+
+    let s = Datatype::from_type::<VarLenAscii>().unwrap();
+    assert!(s.equal_ignoring_order(&Datatype::from_type::<VarLenAscii>().unwrap()));
+    assert!(!s.equal_ignoring_order(&be));
+}
+
+#[test]
+pub fn test_insert_member() -> hdf5::Result<()> {
+    use hdf5_sys::{h5p::H5P_DEFAULT, h5t::H5Tcommit2};
+
+    let dt = Datatype::new_compound(1)?;
+    assert_eq!(dt.nmembers()?, 0);
+    dt.insert_member("a", 0, &Datatype::from_type::<u32>()?)?;
+    dt.insert_member("b", 8, &Datatype::from_type::<f64>()?)?;
+    assert_eq!(dt.nmembers()?, 2);
+    assert_eq!(dt.size(), 16);
+
+    #[derive(H5Type)]
+    #[repr(C)]
+    struct A {
+        a: u32,
+        b: f64,
+    }
+    assert_eq!(dt.to_descriptor()?, A::type_descriptor());
+
+    assert_err!(dt.insert_member("c", 2, &Datatype::from_type::<u8>()?), "unable to insert member");
+    assert_err!(
+        Datatype::from_type::<u32>()?.insert_member("a", 0, &dt),
+        "Expected a compound datatype"
+    );
+
+    let file = new_in_memory_file()?;
+    let name = std::ffi::CString::new("foo").unwrap();
+    let committed = Datatype::new_compound(4)?;
+    unsafe {
+        let (p, f) = (H5P_DEFAULT, file.id());
+        assert!(H5Tcommit2(f, name.as_ptr(), committed.id(), p, p, p) >= 0);
+    }
+    assert_err!(
+        committed.insert_member("a", 0, &Datatype::from_type::<u32>()?),
+        "Cannot modify a committed datatype"
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_set_size() -> hdf5::Result<()> {
+    use hdf5_sys::h5t::{H5Tcopy, H5T_C_S1};
+
+    let _ = Datatype::from_type::<u8>()?; // make sure the library is initialized
+    let dt = unsafe { from_id::<Datatype>(H5Tcopy(*H5T_C_S1)) }?;
+    assert_eq!(dt.size(), 1);
+    dt.set_size(20)?;
+    assert_eq!(dt.size(), 20);
+    assert!(!dt.is_variable_str());
+    assert_eq!(dt.to_descriptor()?, TD::FixedAscii(20));
+
+    dt.set_variable()?;
+    assert!(dt.is_variable_str());
+    assert_eq!(dt.to_descriptor()?, TD::VarLenAscii);
+    assert_err!(dt.set_size(!0), "Use set_variable()");
+
+    let opaque = Datatype::from_type::<u32>()?;
+    assert_err!(opaque.set_variable(), "Expected a string datatype");
+    assert!(!opaque.is_variable_str());
+
+    let file = new_in_memory_file()?;
+    let name = std::ffi::CString::new("foo").unwrap();
+    let committed = Datatype::from_type::<FixedAscii<[u8; 4]>>()?;
+    unsafe {
+        use hdf5_sys::{h5p::H5P_DEFAULT as P, h5t::H5Tcommit2};
+        assert!(H5Tcommit2(file.id(), name.as_ptr(), committed.id(), P, P, P) >= 0);
+    }
+    assert_err!(committed.set_size(8), "Cannot modify a committed datatype");
+    assert_err!(committed.set_variable(), "Cannot modify a committed datatype");
+    assert_eq!(committed.size(), 4);
+    Ok(())
+}
+
+#[test]
+pub fn test_class() -> hdf5::Result<()> {
+    use hdf5_sys::h5t::{
+        H5Tcopy, H5T_NATIVE_B8, H5T_NATIVE_DOUBLE, H5T_NATIVE_OPAQUE, H5T_STD_REF_OBJ,
+        H5T_STD_U16BE, H5T_UNIX_D32LE,
+    };
+
+    let _ = Datatype::from_type::<u8>()?; // make sure the library is initialized
+    let copy = |id| unsafe { from_id::<Datatype>(H5Tcopy(id)) };
+    assert_eq!(copy(*H5T_STD_U16BE)?.class()?, DatatypeClass::Integer);
+    assert_eq!(copy(*H5T_NATIVE_DOUBLE)?.class()?, DatatypeClass::Float);
+    assert_eq!(copy(*H5T_NATIVE_B8)?.class()?, DatatypeClass::Bitfield);
+    assert_eq!(copy(*H5T_NATIVE_OPAQUE)?.class()?, DatatypeClass::Opaque);
+    assert_eq!(copy(*H5T_STD_REF_OBJ)?.class()?, DatatypeClass::Reference);
+    assert_eq!(copy(*H5T_UNIX_D32LE)?.class()?, DatatypeClass::Time);
+
+    assert_eq!(Datatype::from_type::<VarLenAscii>()?.class()?, DatatypeClass::String);
+    assert_eq!(Datatype::from_type::<[u8; 3]>()?.class()?, DatatypeClass::Array);
+    assert_eq!(Datatype::from_type::<VarLenArray<u8>>()?.class()?, DatatypeClass::VarLen);
+    assert_eq!(Datatype::from_type::<bool>()?.class()?, DatatypeClass::Enum);
+    assert_eq!(Datatype::from_type::<(u8, f32)>()?.class()?, DatatypeClass::Compound);
+    Ok(())
+}
+
 #[test]
 pub fn test_debug() {
     assert_eq!(format!("{:?}", Datatype::from_type::<u32>().unwrap()), "<HDF5 datatype>");
+
+    let dt = Datatype::from_type::<FixedAscii<[u8; 20]>>().unwrap();
+    assert_eq!(format!("{:?}", dt), "<HDF5 datatype: 20-byte ASCII string, null-padded>");
+    dt.set_strpad(StringPadding::NullTerminate).unwrap();
+    assert_eq!(format!("{:?}", dt), "<HDF5 datatype: 20-byte ASCII string, null-terminated>");
+    let dt = Datatype::from_type::<VarLenUnicode>().unwrap();
+    assert_eq!(format!("{:?}", dt), "<HDF5 datatype: variable-length UTF-8 string>");
+}
+
+#[test]
+pub fn test_cset() -> hdf5::Result<()> {
+    let dt = Datatype::from_type::<VarLenAscii>()?;
+    assert_eq!(dt.cset()?, CharSet::Ascii);
+    dt.set_cset(CharSet::Utf8)?;
+    assert_eq!(dt.cset()?, CharSet::Utf8);
+    assert_eq!(dt.to_descriptor()?, TD::VarLenUnicode);
+    assert_eq!(Datatype::from_type::<FixedUnicode<[_; 5]>>()?.cset()?, CharSet::Utf8);
+    assert!(Datatype::from_type::<u32>()?.set_cset(CharSet::Utf8).is_err());
+    assert!(Datatype::from_type::<u32>()?.strpad().is_err());
+
+    let file = new_in_memory_file()?;
+    let ds = file.new_dataset::<VarLenUnicode>().create("s", ())?;
+    let s: VarLenUnicode = "héllo→".parse().unwrap();
+    ds.write_scalar(&s)?;
+    assert_eq!(ds.dtype()?.cset()?, CharSet::Utf8);
+    assert_eq!(ds.read_scalar::<VarLenUnicode>()?.as_str(), "héllo→");
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "complex")]
+pub fn test_complex() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let data: Vec<Complex<f64>> =
+        (0..10).map(|i| Complex::new(i as f64, -0.5 * i as f64)).collect();
+    let ds = file.new_dataset::<Complex<f64>>().create("z", data.len())?;
+    ds.write_raw(&data)?;
+    assert_eq!(ds.read_raw::<Complex<f64>>()?, data);
+    match ds.dtype()?.to_descriptor()? {
+        TD::Compound(ref compound) => {
+            let names: Vec<_> = compound.fields.iter().map(|f| f.name.as_str()).collect();
+            assert_eq!(names, vec!["r", "i"]);
+        }
+        ref td => panic!("expected a compound type, got {:?}", td),
+    }
+    Ok(())
+}
+
+#[test]
+pub fn test_pack() -> hdf5::Result<()> {
+    #[derive(H5Type)]
+    #[repr(C)]
+    struct A {
+        a: i8,
+        b: u64,
+        c: i16,
+    }
+
+    let dt = Datatype::from_type::<A>()?;
+    assert_eq!(dt.size(), 24);
+    let packed = dt.pack()?;
+    assert_eq!(packed.size(), 1 + 8 + 2);
+    assert_eq!(dt.size(), 24);
+    assert_eq!(packed.to_descriptor()?, A::type_descriptor().to_packed_repr());
+    assert!(Datatype::from_type::<u32>()?.pack().is_err());
+    Ok(())
+}
+
+#[test]
+pub fn test_describe_full() -> hdf5::Result<()> {
+    use hdf5::{ByteOrder, CompoundMemberDescription as CMD, DatatypeDescription as DD};
+
+    #[derive(H5Type)]
+    #[repr(C)]
+    struct A {
+        a: i32,
+        b: [u16; 3],
+        c: VarLenUnicode,
+    }
+
+    let order =
+        if cfg!(target_endian = "little") { ByteOrder::LittleEndian } else { ByteOrder::BigEndian };
+    let desc = Datatype::from_type::<A>()?.describe_full()?;
+    let expected = DD::Compound {
+        size: mem::size_of::<A>(),
+        members: vec![
+            CMD { name: "a".into(), offset: 0, ty: DD::Integer { size: 4, signed: true, order } },
+            CMD {
+                name: "b".into(),
+                offset: 4,
+                ty: DD::Array {
+                    base: Box::new(DD::Integer { size: 2, signed: false, order }),
+                    dims: vec![3],
+                },
+            },
+            CMD {
+                name: "c".into(),
+                offset: mem::size_of::<A>() - mem::size_of::<VarLenUnicode>(),
+                ty: DD::String { size: None, cset: CharSet::Utf8 },
+            },
+        ],
+    };
+    assert_eq!(desc, expected);
+
+    #[derive(H5Type, Clone, Copy)]
+    #[repr(i16)]
+    #[allow(dead_code)]
+    enum E {
+        X = -1,
+        Y = 300,
+    }
+
+    let desc = Datatype::from_type::<E>()?.describe_full()?;
+    let expected = DD::Enum {
+        base: Box::new(DD::Integer { size: 2, signed: true, order }),
+        members: vec![("X".into(), -1), ("Y".into(), 300)],
+    };
+    assert_eq!(desc, expected);
+    Ok(())
+}
+
+#[test]
+pub fn test_encode_decode() -> hdf5::Result<()> {
+    #[derive(H5Type)]
+    #[repr(C)]
+    struct A {
+        x: i64,
+        y: FixedAscii<[u8; 5]>,
+    }
+
+    let dt = Datatype::from_type::<A>()?;
+    let buf = dt.encode()?;
+    let decoded = Datatype::decode(&buf)?;
+    assert_eq!(decoded, dt);
+    assert_eq!(decoded.nmembers()?, 2);
+    match decoded.to_descriptor()? {
+        TD::Compound(compound) => {
+            let names: Vec<_> = compound.fields.iter().map(|f| f.name.as_str()).collect();
+            assert_eq!(names, vec!["x", "y"]);
+        }
+        desc => panic!("expected a compound, got {:?}", desc),
+    }
+    assert_err!(Datatype::decode(&[]), "cannot decode datatype from an empty buffer");
+    Ok(())
+}
+
+#[test]
+pub fn test_array_of_vlen_of() -> hdf5::Result<()> {
+    use hdf5::DatatypeDescription as DD;
+
+    let base = Datatype::from_type::<i32>()?;
+    let arr = base.array_of(&[4])?;
+    assert_eq!(arr.class()?, DatatypeClass::Array);
+    assert_eq!(arr.size(), 16);
+    assert_eq!(arr.super_type()?, base);
+    match arr.describe_full()? {
+        DD::Array { dims, .. } => assert_eq!(dims, vec![4]),
+        desc => panic!("expected an array, got {:?}", desc),
+    }
+    assert_eq!(arr, Datatype::from_type::<[i32; 4]>()?);
+    assert_eq!(base.array_of(&[2, 3])?.size(), 24);
+    assert_err!(base.array_of(&[]), "array datatype must have at least one dimension");
+
+    let vlen = Datatype::from_type::<f32>()?.vlen_of()?;
+    assert_eq!(vlen.class()?, DatatypeClass::VarLen);
+    assert_eq!(vlen, Datatype::from_type::<VarLenArray<f32>>()?);
+    assert_eq!(vlen.super_type()?, Datatype::from_type::<f32>()?);
+    assert!(base.super_type().is_err());
+    Ok(())
+}
+
+#[test]
+pub fn test_offset_of() -> hdf5::Result<()> {
+    #[derive(H5Type)]
+    #[repr(C)]
+    struct A {
+        a: u8,
+        b: f64,
+        c: [u16; 3],
+    }
+
+    assert_eq!(hdf5::h5_offset_of!(A, a), 0);
+    assert_eq!(hdf5::h5_offset_of!(A, b), 8);
+    assert_eq!(hdf5::h5_offset_of!(A, c), 16);
+
+    let dt = Datatype::new_compound(mem::size_of::<A>())?;
+    dt.insert_member("a", hdf5::h5_offset_of!(A, a), &Datatype::from_type::<u8>()?)?;
+    dt.insert_member("b", hdf5::h5_offset_of!(A, b), &Datatype::from_type::<f64>()?)?;
+    dt.insert_member("c", hdf5::h5_offset_of!(A, c), &Datatype::from_type::<[u16; 3]>()?)?;
+    assert_eq!(dt.size(), mem::size_of::<A>());
+    assert_eq!(dt.to_descriptor()?, A::type_descriptor());
+    Ok(())
+}
+
+#[test]
+pub fn test_of_native_of() -> hdf5::Result<()> {
+    assert_eq!(Datatype::of::<f64>()?, Datatype::from_type::<f64>()?);
+    assert_eq!(Datatype::of::<[u16; 3]>()?, Datatype::from_type::<[u16; 3]>()?);
+    assert_eq!(Datatype::native_of::<f64>()?, Datatype::from_type::<f64>()?);
+    assert_eq!(Datatype::native_of::<i32>()?.size(), 4);
+    assert!(Datatype::native_of::<u8>()?.is::<u8>());
+    Ok(())
+}
+
+#[test]
+pub fn test_native_type() -> hdf5::Result<()> {
+    assert_eq!(Datatype::from_type::<u16>()?.native_type()?, Datatype::from_type::<u16>()?);
+
+    let dt = Datatype::from_type::<i32>()?;
+    dt.set_precision(24)?;
+    dt.set_size(3)?;
+    assert_eq!(dt.size(), 3);
+    let native = dt.native_type()?;
+    assert!(native.size() >= 4);
+    assert_eq!(native.to_descriptor()?, TD::Integer(IntSize::U4));
+    assert_err!(dt.to_descriptor(), "Invalid size of integer datatype");
+    Ok(())
+}
+
+#[test]
+pub fn test_read_24bit_big_endian() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let dt = unsafe {
+        use hdf5_sys::h5t::{H5Tcopy, H5T_STD_I32BE};
+        from_id::<Datatype>(H5Tcopy(*H5T_STD_I32BE))?
+    };
+    dt.set_precision(24)?;
+    dt.set_size(3)?;
+    let space = hdf5::Dataspace::try_new(4, false)?;
+    let name = std::ffi::CString::new("foo").unwrap();
+    unsafe {
+        use hdf5_sys::{h5d::H5Dcreate2, h5p::H5P_DEFAULT as P};
+        from_id::<hdf5::Dataset>(H5Dcreate2(
+            file.id(),
+            name.as_ptr(),
+            dt.id(),
+            space.id(),
+            P,
+            P,
+            P,
+        ))?;
+    }
+
+    // the file type is converted by the library, no native type is needed for the buffer
+    let ds = file.dataset("foo")?;
+    assert_eq!(ds.dtype()?.size(), 3);
+    let data = vec![1, -2, 0x7f_ffff, -0x80_0000];
+    ds.write_raw(&data)?;
+    assert_eq!(ds.read_raw::<i32>()?, data);
+    assert_eq!(ds.read_raw::<i64>()?, vec![1, -2, 0x7f_ffff, -0x80_0000]);
+    assert_eq!(ds.dtype()?.native_type()?.size(), 4);
+    Ok(())
+}
+
+#[test]
+pub fn test_float_fields() -> hdf5::Result<()> {
+    use hdf5::{BitPadding, FloatFields, FloatNormalization};
+
+    let dt = Datatype::from_type::<f32>()?;
+    let fields = FloatFields { spos: 31, epos: 23, esize: 8, mpos: 0, msize: 23 };
+    assert_eq!(dt.float_fields()?, fields);
+    assert_eq!(dt.ebias()?, 127);
+    assert_eq!(dt.norm()?, FloatNormalization::Implied);
+    assert_eq!(dt.inpad()?, BitPadding::Zero);
+    assert_eq!(format!("{:?}", dt), "<HDF5 datatype>");
+
+    let dt = Datatype::from_type::<f64>()?;
+    let fields = FloatFields { spos: 63, epos: 52, esize: 11, mpos: 0, msize: 52 };
+    assert_eq!(dt.float_fields()?, fields);
+    assert_eq!(dt.ebias()?, 1023);
+
+    let dt = Datatype::from_type::<i32>()?;
+    assert_err!(dt.float_fields(), "Expected a float datatype");
+    assert_err!(dt.ebias(), "Expected a float datatype");
+    Ok(())
+}
+
+#[test]
+pub fn test_custom_float() -> hdf5::Result<()> {
+    use hdf5::{BitPadding, FloatFields, FloatNormalization};
+
+    let dt = Datatype::from_type::<f32>()?;
+    assert_eq!(dt.precision()?, 32);
+    let fields = FloatFields { spos: 15, epos: 10, esize: 5, mpos: 0, msize: 10 };
+    dt.set_float_fields(fields)?;
+    dt.set_precision(16)?;
+    dt.set_size(2)?;
+    dt.set_ebias(15)?;
+    dt.set_norm(FloatNormalization::Implied)?;
+    dt.set_inpad(BitPadding::Zero)?;
+    assert_eq!(dt.size(), 2);
+    assert_eq!(dt.precision()?, 16);
+    assert_eq!(dt.float_fields()?, fields);
+    assert_eq!(dt.ebias()?, 15);
+    assert_eq!(
+        format!("{:?}", dt),
+        "<HDF5 datatype: non-standard 2-byte float (sign 15, exponent 10+5, mantissa 0+10, bias 15)>"
+    );
+    assert!(dt.conv_from::<f32>().is_some());
+    dt.set_ebias(0)?;
+    assert_eq!(dt.ebias()?, 0);
+    dt.set_ebias(15)?;
+
+    assert_err!(Datatype::from_type::<u8>()?.set_ebias(1), "Expected a float datatype");
+
+    let file = new_in_memory_file()?;
+    let name = std::ffi::CString::new("foo").unwrap();
+    let committed = Datatype::from_type::<f64>()?;
+    unsafe {
+        use hdf5_sys::{h5p::H5P_DEFAULT as P, h5t::H5Tcommit2};
+        assert!(H5Tcommit2(file.id(), name.as_ptr(), committed.id(), P, P, P) >= 0);
+    }
+    assert_err!(committed.set_ebias(511), "Cannot modify a committed datatype");
+    assert_err!(committed.set_precision(32), "Cannot modify a committed datatype");
+    assert_eq!(committed.ebias()?, 1023);
+    Ok(())
+}
+
+#[test]
+pub fn test_enum_lookup() -> hdf5::Result<()> {
+    #[derive(H5Type, Clone, Copy)]
+    #[repr(i16)]
+    #[allow(dead_code)]
+    enum Color {
+        Red = -1,
+        Green = 2,
+        Blue = 300,
+    }
+
+    let dt = Datatype::from_type::<Color>()?;
+    assert_eq!(
+        dt.enum_members()?,
+        vec![("Red".to_owned(), -1), ("Green".to_owned(), 2), ("Blue".to_owned(), 300)]
+    );
+    assert_eq!(dt.enum_value_of("Red")?, -1);
+    assert_eq!(dt.enum_value_of("Blue")?, 300);
+    assert_eq!(dt.enum_name_of(2)?, "Green");
+    assert_eq!(dt.enum_name_of(300)?, "Blue");
+    assert!(dt.enum_value_of("Yellow").is_err());
+    assert!(dt.enum_name_of(3).is_err());
+    assert_err!(dt.enum_name_of(1 << 20), "Value out of range for enum datatype");
+
+    // member values are stored in the byte order of the base type
+    let dt = unsafe {
+        use hdf5_sys::h5t::{H5Tenum_create, H5Tenum_insert, H5T_STD_I16BE};
+        let dt = from_id::<Datatype>(H5Tenum_create(*H5T_STD_I16BE))?;
+        for &(name, value) in &[("Low", -2_i16), ("High", 0x1234)] {
+            let name = std::ffi::CString::new(name).unwrap();
+            let value = value.to_be_bytes();
+            assert!(H5Tenum_insert(dt.id(), name.as_ptr(), value.as_ptr() as *const _) >= 0);
+        }
+        dt
+    };
+    assert_eq!(dt.enum_members()?, vec![("Low".to_owned(), -2), ("High".to_owned(), 0x1234)]);
+    assert_eq!(dt.enum_value_of("High")?, 0x1234);
+    assert_eq!(dt.enum_name_of(-2)?, "Low");
+    assert_eq!(dt.enum_name_of(0x1234)?, "High");
+
+    let dt = Datatype::from_type::<bool>()?;
+    assert_eq!(dt.enum_name_of(1)?, "TRUE");
+    assert_err!(dt.enum_name_of(1000), "Value out of range");
+    assert_err!(Datatype::from_type::<u8>()?.enum_members(), "Expected an enum datatype");
+    Ok(())
+}
+
+#[test]
+pub fn test_member_by_path() -> hdf5::Result<()> {
+    #[derive(H5Type, Clone, Copy)]
+    #[repr(C)]
+    struct Inner {
+        x: i16,
+        y: [f32; 2],
+    }
+
+    #[derive(H5Type, Clone, Copy)]
+    #[repr(C)]
+    struct Outer {
+        a: u8,
+        inner: Inner,
+    }
+
+    let dt = Datatype::from_type::<Outer>()?;
+    assert_eq!(dt.member_by_path("a")?, Datatype::from_type::<u8>()?);
+    assert_eq!(dt.member_by_path("inner")?, Datatype::from_type::<Inner>()?);
+    assert_eq!(dt.member_by_path("inner.x")?, Datatype::from_type::<i16>()?);
+    assert_eq!(dt.member_by_path("inner.y")?, Datatype::from_type::<[f32; 2]>()?);
+    assert_err!(dt.member_by_path("inner.z"), "Compound member not found: \"inner.z\"");
+    assert_err!(dt.member_by_path("b"), "Compound member not found: \"b\"");
+    assert_err!(
+        dt.member_by_path("inner.x.foo"),
+        "Expected a compound datatype at \"inner.x\" in member path \"inner.x.foo\""
+    );
+    assert_err!(dt.member_by_path(""), "Compound member not found: \"\"");
+    assert_err!(
+        Datatype::from_type::<u8>()?.member_by_path("a"),
+        "Expected a compound datatype at \"\" in member path \"a\""
+    );
+    Ok(())
 }
@@ -546,6 +546,60 @@ fn test_dapl_set_efile_prefix() -> hdf5::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_dapl_set_elink_prefix() -> hdf5::Result<()> {
+    assert_eq!(DA::try_new()?.get_elink_prefix()?, "".to_owned());
+    assert_eq!(DA::try_new()?.elink_prefix(), "".to_owned());
+    let mut b = DA::build();
+    b.elink_prefix("foo");
+    assert_eq!(b.finish()?.get_elink_prefix()?, "foo".to_owned());
+    assert_eq!(DAB::from_plist(&b.finish()?)?.finish()?.elink_prefix(), "foo".to_owned());
+    Ok(())
+}
+
+#[test]
+fn test_dapl_set_elink_fapl() -> hdf5::Result<()> {
+    assert!(DA::try_new()?.get_elink_fapl()?.is_none());
+    let fapl = FA::build().fclose_degree(FileCloseDegree::Strong).finish()?;
+    let dapl = DA::build().elink_fapl(&fapl).finish()?;
+    assert_eq!(dapl.elink_fapl().unwrap().fclose_degree(), FileCloseDegree::Strong);
+    let dapl = DAB::from_plist(&dapl)?.finish()?;
+    assert_eq!(dapl.get_elink_fapl()?.unwrap().fclose_degree(), FileCloseDegree::Strong);
+    Ok(())
+}
+
+#[test]
+fn test_dapl_elink_resolution() -> hdf5::Result<()> {
+    use hdf5_sys::{h5l::H5Lcreate_external, h5p::H5P_DEFAULT};
+
+    let dir = tempdir::TempDir::new("elink").unwrap();
+    let sub = dir.path().join("sub");
+    std::fs::create_dir(&sub).unwrap();
+    let target = hdf5::File::create(sub.join("target.h5"))?;
+    target.new_dataset::<u16>().create("data", 3)?.write_raw(&[1, 2, 3])?;
+    drop(target);
+
+    let file = hdf5::File::create(dir.path().join("main.h5"))?;
+    let (name, path, link) = (
+        std::ffi::CString::new("target.h5").unwrap(),
+        std::ffi::CString::new("/data").unwrap(),
+        std::ffi::CString::new("ext").unwrap(),
+    );
+    unsafe {
+        let (n, p, l, d) = (name.as_ptr(), path.as_ptr(), link.as_ptr(), H5P_DEFAULT);
+        assert!(H5Lcreate_external(n, p, file.id(), l, d, d) >= 0);
+    }
+    assert!(file.dataset("ext").is_err());
+
+    let prefix = sub.to_str().unwrap();
+    let fapl = FA::build().fclose_degree(FileCloseDegree::Weak).finish()?;
+    let dapl = DA::build().elink_prefix(prefix).elink_fapl(&fapl).finish()?;
+    let ds = file.dataset_with_access("ext", &dapl)?;
+    assert_eq!(ds.read_raw::<u16>()?, vec![1, 2, 3]);
+    assert!(ds.filename().ends_with("target.h5"));
+    Ok(())
+}
+
 #[test]
 fn test_dapl_set_chunk_cache() -> hdf5::Result<()> {
     test_pl!(DA, chunk_cache: nslots = 1, nbytes = 100, w0 = 0.0);
@@ -577,3 +631,106 @@ fn test_dapl_set_virtual_printf_gap() -> hdf5::Result<()> {
     test_pl!(DA, virtual_printf_gap: 123);
     Ok(())
 }
+
+type DT = DatasetTransfer;
+type DTB = DatasetTransferBuilder;
+
+#[test]
+fn test_dxpl_common() -> hdf5::Result<()> {
+    test_pl_common!(DT, PropertyListClass::DataTransfer, |b: &mut DTB| b
+        .buffer_size(64 * 1024)
+        .finish());
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_set_buffer_size() -> hdf5::Result<()> {
+    test_pl!(DT, buffer_size: 1);
+    test_pl!(DT, buffer_size: 64 * 1024);
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_set_preserve() -> hdf5::Result<()> {
+    test_pl!(DT, preserve: true);
+    test_pl!(DT, preserve: false);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "mpio")]
+fn test_dxpl_set_collective() -> hdf5::Result<()> {
+    test_pl!(DT, collective: true);
+    test_pl!(DT, collective: false);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "mpio")]
+fn test_dxpl_collective_write() -> hdf5::Result<()> {
+    use std::os::raw::c_int;
+    use std::ptr;
+
+    use mpi_sys::{MPI_Init, MPI_Initialized, RSMPI_COMM_SELF};
+
+    let mut initialized: c_int = 1;
+    unsafe { MPI_Initialized(&mut initialized) };
+    if initialized == 0 {
+        unsafe { MPI_Init(ptr::null_mut(), ptr::null_mut()) };
+    }
+
+    let dir = tempdir::TempDir::new("mpio").unwrap();
+    let path = dir.path().join("foo.h5");
+    let comm = unsafe { RSMPI_COMM_SELF };
+    let file = hdf5::File::with_options().with_fapl(|p| p.mpio(comm, None)).create(&path)?;
+    let ds = file.new_dataset::<i32>().no_chunk().create("foo", 4)?;
+    let dxpl = DatasetTransfer::build().collective(true).finish()?;
+    ds.as_writer().dxpl(&dxpl).write_raw(&[1, 2, 3, 4])?;
+    assert_eq!(ds.as_reader().dxpl(&dxpl).read_raw::<i32>()?, vec![1, 2, 3, 4]);
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_set_conversion_callback() -> hdf5::Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static RANGE_HIGH: AtomicUsize = AtomicUsize::new(0);
+
+    fn record(except: ConversionException) -> ConversionAction {
+        if except == ConversionException::RangeHigh {
+            RANGE_HIGH.fetch_add(1, Ordering::SeqCst);
+        }
+        ConversionAction::Continue
+    }
+
+    fn abort(_: ConversionException) -> ConversionAction {
+        ConversionAction::Abort
+    }
+
+    fn panicking(_: ConversionException) -> ConversionAction {
+        panic!("conversion callback panicked")
+    }
+
+    assert!(DT::try_new()?.conversion_callback().is_none());
+    let dxpl = DT::build().conversion_callback(record).finish()?;
+    assert!(dxpl.conversion_callback().is_some());
+    assert!(DTB::from_plist(&dxpl)?.finish()?.conversion_callback().is_some());
+
+    let dir = tempdir::TempDir::new("dxpl").unwrap();
+    let file = hdf5::File::create(dir.path().join("foo.h5"))?;
+    let ds = file.new_dataset::<f64>().create("foo", 3)?;
+    ds.write_raw(&[1.0, 1e300, 2.0])?;
+
+    let data = ds.as_reader().dxpl(&dxpl).read_raw::<f32>()?;
+    assert_eq!(RANGE_HIGH.load(Ordering::SeqCst), 1);
+    assert_eq!((data[0], data[2]), (1.0, 2.0));
+    assert!(data[1].is_infinite());
+
+    let dxpl = DT::build().conversion_callback(abort).finish()?;
+    assert!(ds.as_reader().dxpl(&dxpl).read_raw::<f32>().is_err());
+    assert_eq!(ds.as_reader().dxpl(&dxpl).read_raw::<f64>()?, vec![1.0, 1e300, 2.0]);
+
+    let dxpl = DT::build().conversion_callback(panicking).finish()?;
+    assert!(ds.as_reader().dxpl(&dxpl).read_raw::<f32>().is_err());
+    Ok(())
+}